@@ -1,24 +1,201 @@
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use crossterm::event::KeyCode;
 use serde_json::Value;
-
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::clipboard;
 use crate::config::Config;
-use crate::gcp::client::GcpClient;
-use crate::gcp::dispatch::{execute_action, list_resources};
+use crate::describe::{DescribeView, DocFormat};
+use crate::export::{render_export, ExportMeta, ExportRequest};
+use crate::filter::Filter;
+use crate::fuzzy;
+use crate::gcp::auth::{TokenIdentity, TokenProvider};
+use crate::gcp::client::{
+    derive_region, parse_region, region_label, ComputeLocation, GcpClient, ALL_ZONES,
+};
+use crate::gcp::dispatch::{
+    confirm_message, console_url, describe_resource, execute_action, item_url, list_resources,
+    ListProgress,
+};
+use crate::gcp::operations::{
+    detect_operation, parse_status, spawn_poller, OperationRef, OperationState, OperationStatus,
+    OperationUpdate, TrackedOperation,
+};
+use crate::gcp::resourcemanager::{Container, ProjectInfo};
+use crate::projects::{PickerRow, ProjectPicker, ProjectView};
 use crate::resource::registry::{
-    extract_json_value, get_all_resource_keys, get_resource, ResourceDef,
+    extract_json_value, get_all_regions_resource, get_all_resource_keys, get_resource,
+    registry_report, ParamDef, ParamType, ResourceDef,
 };
+use crate::resource::sort::{sort_items, SortState};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Mode {
-    Normal,   // Viewing list
-    Command,  // : command input
-    Help,     // ? help popup
-    Confirm,  // Confirmation dialog
-    Warning,  // Warning/info dialog (OK only)
-    Projects, // Project selection
-    Zones,    // Zone selection
-    Describe, // Viewing JSON details of selected item
+    Normal,     // Viewing list
+    Command,    // : command input
+    Help,       // ? help popup
+    Confirm,    // Confirmation dialog
+    Warning,    // Warning/info dialog (OK only)
+    Projects,   // Project selection
+    Zones,      // Zone selection
+    Describe,   // Viewing JSON details of selected item
+    Input,      // Action parameter input form
+    Operations, // Long-running operations panel
+}
+
+/// How long a notification stays in the crumb
+const NOTIFICATION_TTL: Duration = Duration::from_secs(5);
+
+/// Short-lived status message shown in the crumb
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub message: String,
+    pub is_error: bool,
+    pub created: Instant,
+}
+
+/// Result of a background request, sent from a spawned task to the app
+#[derive(Debug)]
+pub enum TaskMessage {
+    /// A page of a list call arrived
+    ListProgress {
+        request_id: u64,
+        progress: ListProgress,
+    },
+    /// A list call finished
+    Listed {
+        request_id: u64,
+        result: Result<Vec<Value>, String>,
+    },
+    /// A describe (get) call finished
+    Described {
+        request_id: u64,
+        result: Result<Value, String>,
+    },
+    /// An action call finished
+    ActionFinished {
+        /// Action and target, e.g. "Stop 'vm-1'"
+        description: String,
+        /// Resource the action was run on
+        resource_key: String,
+        /// API base of that resource (resolves relative operation names)
+        api_base: String,
+        result: Result<Value, String>,
+    },
+    /// Every request of a bulk action finished
+    BulkFinished {
+        /// Action and target count, e.g. "Stop 5 VM Instances"
+        description: String,
+        /// Action name used to describe operations of single items
+        action_name: String,
+        resource_key: String,
+        api_base: String,
+        /// Item name and result, in target order
+        results: Vec<(String, Result<Value, String>)>,
+    },
+    /// Projects visible to new credentials were fetched
+    ProjectsListed {
+        result: Result<Vec<ProjectInfo>, String>,
+    },
+    /// Organizations and folders of the project picker were fetched
+    ContainersListed {
+        result: Result<Vec<Container>, String>,
+    },
+    /// Zones or regions of a project were fetched
+    LocationsListed {
+        project: String,
+        kind: LocationKind,
+        result: Result<Vec<ComputeLocation>, String>,
+    },
+    /// A token was fetched for :whoami
+    Whoami {
+        result: Result<TokenIdentity, String>,
+    },
+}
+
+/// What the zone picker lists
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LocationKind {
+    Zone,
+    Region,
+}
+
+impl LocationKind {
+    pub fn plural(&self) -> &'static str {
+        match self {
+            LocationKind::Zone => "zones",
+            LocationKind::Region => "regions",
+        }
+    }
+}
+
+/// Zones offered by the picker when the Compute Engine API can't list them
+const FALLBACK_ZONES: &[&str] = &[
+    "us-central1-a",
+    "us-central1-b",
+    "us-central1-c",
+    "us-east1-b",
+    "us-east1-c",
+    "us-west1-a",
+    "us-west1-b",
+    "europe-west1-b",
+    "europe-west1-c",
+    "asia-east1-a",
+    "asia-east1-b",
+    "asia-northeast1-a",
+];
+
+/// Text of the :whoami dialog
+fn whoami_message(identity: &TokenIdentity, now: chrono::DateTime<chrono::Utc>) -> String {
+    let remaining = (identity.expires_at - now).num_minutes();
+    let expires = if remaining >= 0 {
+        format!("in {} min", remaining)
+    } else {
+        "expired".to_string()
+    };
+    let mut lines = vec![
+        format!(
+            "Principal: {}",
+            identity.principal.as_deref().unwrap_or("unknown")
+        ),
+        format!("Source: {}", identity.source),
+        format!(
+            "Expires: {} ({})",
+            identity.expires_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            expires
+        ),
+    ];
+    if identity.scopes.is_empty() {
+        lines.push("Scopes: unknown".to_string());
+    } else {
+        lines.push("Scopes:".to_string());
+        lines.extend(identity.scopes.iter().cloned());
+    }
+    lines.join("\n")
+}
+
+/// Add the current, pinned and recent projects if the listed ones miss them,
+/// so they stay selectable
+fn with_known_projects(
+    mut projects: Vec<ProjectInfo>,
+    current: &str,
+    config: &Config,
+) -> Vec<ProjectInfo> {
+    let known = std::iter::once(current)
+        .chain(config.pinned_projects.iter().map(|p| p.as_str()))
+        .chain(config.recent_projects.iter().map(|p| p.as_str()));
+    for id in known {
+        if !id.is_empty() && !projects.iter().any(|p| p.project_id == id) {
+            projects.push(ProjectInfo::from_id(id));
+        }
+    }
+    projects
 }
 
 /// Pending action that requires confirmation
@@ -32,8 +209,106 @@ pub struct PendingAction {
     pub selected_yes: bool,
     /// Action data
     pub action_key: String,
-    #[allow(dead_code)]
-    pub resource_id: String,
+    /// Items the action runs on, more than one for a bulk action
+    pub targets: Vec<Value>,
+    /// Validated parameter values collected from the input form
+    pub params: HashMap<String, Value>,
+}
+
+/// Input form collecting action parameters before dispatch
+#[derive(Debug, Clone)]
+pub struct ActionForm {
+    /// Index of the action in the resource definition
+    pub action_index: usize,
+    /// Dialog title (action name and target)
+    pub title: String,
+    pub fields: Vec<FormField>,
+    /// Index of the focused field
+    pub focused: usize,
+    /// Validation error from the last submit
+    pub error: Option<String>,
+}
+
+/// Single field of an action input form
+#[derive(Debug, Clone)]
+pub struct FormField {
+    pub param: ParamDef,
+    pub value: String,
+}
+
+impl ActionForm {
+    pub fn next_field(&mut self) {
+        if !self.fields.is_empty() {
+            self.focused = (self.focused + 1) % self.fields.len();
+        }
+    }
+
+    pub fn prev_field(&mut self) {
+        if !self.fields.is_empty() {
+            self.focused = (self.focused + self.fields.len() - 1) % self.fields.len();
+        }
+    }
+
+    /// Type a character into the focused field (enum fields are cycled instead)
+    pub fn push_char(&mut self, c: char) {
+        if let Some(field) = self.fields.get_mut(self.focused) {
+            if field.param.param_type != ParamType::Enum {
+                field.value.push(c);
+                self.error = None;
+            }
+        }
+    }
+
+    pub fn pop_char(&mut self) {
+        if let Some(field) = self.fields.get_mut(self.focused) {
+            if field.param.param_type != ParamType::Enum {
+                field.value.pop();
+                self.error = None;
+            }
+        }
+    }
+
+    /// Cycle the focused enum field through its options
+    pub fn cycle_option(&mut self, forward: bool) {
+        let Some(field) = self.fields.get_mut(self.focused) else {
+            return;
+        };
+        let options = &field.param.options;
+        if field.param.param_type != ParamType::Enum || options.is_empty() {
+            return;
+        }
+        let current = options.iter().position(|o| o == &field.value);
+        let next = match (current, forward) {
+            (Some(i), true) => (i + 1) % options.len(),
+            (Some(i), false) => (i + options.len() - 1) % options.len(),
+            (None, _) => 0,
+        };
+        field.value = options[next].clone();
+        self.error = None;
+    }
+
+    /// Validate every field, returning the typed values keyed by param name
+    pub fn validate(&self) -> Result<HashMap<String, Value>, String> {
+        let mut values = HashMap::new();
+        for field in &self.fields {
+            let value = field.param.parse_value(&field.value)?;
+            values.insert(field.param.name.clone(), value);
+        }
+        Ok(values)
+    }
+}
+
+/// Part of an item copied to the clipboard by a yank
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum YankTarget {
+    Name,
+    Id,
+    /// selfLink, or the API URL of the item
+    Link,
+    /// Cell of the column at this index
+    Column(usize),
+    /// The whole item
+    Json,
 }
 
 /// Parent context for hierarchical navigation
@@ -60,9 +335,13 @@ pub struct App {
 
     // Navigation state
     pub selected: usize,
+    pub marked: HashSet<String>, // Keys of items marked for a bulk action
     pub mode: Mode,
     pub filter_text: String,
     pub filter_active: bool,
+    pub filter: Filter,                // Parsed filter_text
+    pub server_filter: Option<String>, // Expression sent to the list API, None uses the default
+    pub sort: Option<SortState>,       // Column the table is sorted by, None keeps API order
 
     // Hierarchical navigation
     pub parent_context: Option<ParentContext>,
@@ -77,74 +356,102 @@ pub struct App {
     // Project/Zone
     pub project: String,
     pub zone: String,
-    pub available_projects: Vec<String>,
-    pub available_zones: Vec<String>,
-    pub projects_selected: usize,
-    pub zones_selected: usize,
+    pub region: String, // Region of regional resources, ALL_REGIONS for every region
+    pub available_projects: Vec<ProjectInfo>,
+    pub project_picker: ProjectPicker, // Search, view and selection of the project picker
+    pub available_zones: Vec<ComputeLocation>, // Fetched when the zone picker first opens
+    pub available_regions: Vec<ComputeLocation>,
+    pub zones_selected: usize,       // Index into filtered_locations()
+    pub location_kind: LocationKind, // Whether the zone picker lists zones or regions
+    pub location_query: String,      // Fuzzy search of the zone picker
+    pub location_searching: bool,    // Typing goes to the picker search (after /)
+    pub locations_loading: bool,
 
     // Confirmation
     pub pending_action: Option<PendingAction>,
 
+    // Action parameter input
+    pub action_form: Option<ActionForm>,
+
+    // Long-running operations started by actions (newest first)
+    pub operations: Vec<TrackedOperation>,
+    pub operations_selected: usize,
+    pub next_operation_id: usize,
+    pub operation_tx: UnboundedSender<OperationUpdate>,
+    pub operation_rx: UnboundedReceiver<OperationUpdate>,
+
+    // Results of background list/action requests
+    pub task_tx: UnboundedSender<TaskMessage>,
+    pub task_rx: UnboundedReceiver<TaskMessage>,
+    pub list_request_id: u64, // Id of the latest list request, older results are stale
+
+    // Transient message shown in the crumb (e.g., operation finished)
+    pub notification: Option<Notification>,
+
     // UI state
     pub loading: bool,
+    pub list_progress: Option<ListProgress>, // Pagination progress of the last list call
     pub error: Option<String>,
-    pub describe_scroll: usize,
+    pub describe_view: DescribeView, // Cursor, folds and search of the describe view
     pub describe_data: Option<Value>, // Full resource details from describe API
+    pub describe_loading: bool,      // Full resource is being fetched, list item shown meanwhile
+    pub describe_error: Option<String>, // Fetching the full resource failed
+    pub describe_request_id: u64,    // Id of the latest describe request
 
     // Auto-refresh
     pub last_refresh: std::time::Instant,
+    pub refresh_requested: bool, // Refresh on next tick regardless of timer
 
     // Key press tracking for sequences (e.g., 'gg')
     pub last_key_press: Option<(KeyCode, std::time::Instant)>,
+    pub yank_pending: bool, // 'y' pressed, waiting for what to copy
 
     // Warning message for modal dialog
     pub warning_message: Option<String>,
+    // Information for modal dialog (e.g., :whoami)
+    pub info_message: Option<String>,
+    // First message line shown by the confirm and warning dialogs
+    pub dialog_scroll: usize,
+    // Largest useful dialog_scroll, updated by the renderer
+    pub dialog_max_scroll: Cell<usize>,
 
     // Configuration (persisted to disk)
     pub config: Config,
 
     // Read-only mode (blocks all write operations)
     pub readonly: bool,
+    pub cli_readonly: bool, // --readonly, kept when switching contexts
 }
 
 impl App {
     pub async fn new(
         zone: Option<String>,
+        region: Option<String>,
         project: Option<String>,
         config: Config,
         readonly: bool,
     ) -> Result<Self> {
-        let client = GcpClient::new(zone.clone(), project.clone()).await?;
+        let mut client = GcpClient::new(zone.clone(), project.clone()).await?;
+        client.max_pages = config.effective_max_pages();
+        if let Some(region) = region {
+            client.region = parse_region(&region);
+        }
         let project = client.project.clone();
         let zone = client.zone.clone();
-
-        // Default available zones (can be fetched from API later)
-        let available_zones = vec![
-            "us-central1-a".to_string(),
-            "us-central1-b".to_string(),
-            "us-central1-c".to_string(),
-            "us-east1-b".to_string(),
-            "us-east1-c".to_string(),
-            "us-west1-a".to_string(),
-            "us-west1-b".to_string(),
-            "europe-west1-b".to_string(),
-            "europe-west1-c".to_string(),
-            "asia-east1-a".to_string(),
-            "asia-east1-b".to_string(),
-            "asia-northeast1-a".to_string(),
-        ];
+        let region = client.region.clone();
 
         // Fetch available projects from GCP API
-        let available_projects = match client.list_projects().await {
-            Ok(projects) => {
-                if projects.is_empty() {
-                    vec![project.clone()]
-                } else {
-                    projects
-                }
+        let listed = match client.list_projects().await {
+            Ok(projects) => projects,
+            Err(e) => {
+                tracing::warn!("Failed to list projects: {}", e);
+                Vec::new()
             }
-            Err(_) => vec![project.clone()],
         };
+        let available_projects = with_known_projects(listed, &project, &config);
+
+        let (operation_tx, operation_rx) = unbounded_channel();
+        let (task_tx, task_rx) = unbounded_channel();
 
         // If no project is set, start in Projects mode to let user select one
         let initial_mode = if project.is_empty() {
@@ -153,15 +460,19 @@ impl App {
             Mode::Normal
         };
 
-        Ok(Self {
+        let mut app = Self {
             client,
             resource_key: "vm-instances".to_string(),
             items: Vec::new(),
             filtered_items: Vec::new(),
             selected: 0,
+            marked: HashSet::new(),
             mode: initial_mode,
             filter_text: String::new(),
             filter_active: false,
+            filter: Filter::default(),
+            server_filter: None,
+            sort: default_sort("vm-instances"),
             parent_context: None,
             navigation_stack: Vec::new(),
             command_text: String::new(),
@@ -170,21 +481,58 @@ impl App {
             command_preview: None,
             project,
             zone,
+            region,
             available_projects,
-            available_zones,
-            projects_selected: 0,
+            available_zones: Vec::new(),
+            available_regions: Vec::new(),
+            project_picker: ProjectPicker::default(),
             zones_selected: 0,
+            location_kind: LocationKind::Zone,
+            location_query: String::new(),
+            location_searching: false,
+            locations_loading: false,
             pending_action: None,
+            action_form: None,
+            operations: Vec::new(),
+            operations_selected: 0,
+            next_operation_id: 0,
+            operation_tx,
+            operation_rx,
+            task_tx,
+            task_rx,
+            list_request_id: 0,
+            notification: None,
             loading: false,
+            list_progress: None,
             error: None,
-            describe_scroll: 0,
+            describe_view: DescribeView::default(),
             describe_data: None,
+            describe_loading: false,
+            describe_error: None,
+            describe_request_id: 0,
             last_refresh: std::time::Instant::now(),
+            refresh_requested: false,
             last_key_press: None,
+            yank_pending: false,
             warning_message: None,
+            info_message: None,
+            dialog_scroll: 0,
+            dialog_max_scroll: Cell::new(0),
+            readonly: readonly || config.context_readonly(),
+            cli_readonly: readonly,
             config,
-            readonly,
-        })
+        };
+
+        // Report resource definitions that failed to load
+        if let Some(report) = registry_report(5) {
+            if app.mode == Mode::Normal {
+                app.show_warning(&report);
+            } else {
+                app.notify("Some resource definitions failed to load, see log", true);
+            }
+        }
+
+        Ok(app)
     }
 
     /// Check if a project is selected
@@ -199,13 +547,16 @@ impl App {
         client: GcpClient,
         project: String,
         zone: String,
-        available_projects: Vec<String>,
-        available_zones: Vec<String>,
+        available_projects: Vec<ProjectInfo>,
+        available_zones: Vec<ComputeLocation>,
         initial_items: Vec<Value>,
         config: Config,
         readonly: bool,
     ) -> Self {
         let filtered_items = initial_items.clone();
+        let region = client.region.clone();
+        let (operation_tx, operation_rx) = unbounded_channel();
+        let (task_tx, task_rx) = unbounded_channel();
 
         Self {
             client,
@@ -213,9 +564,13 @@ impl App {
             items: initial_items,
             filtered_items,
             selected: 0,
+            marked: HashSet::new(),
             mode: Mode::Normal,
             filter_text: String::new(),
             filter_active: false,
+            filter: Filter::default(),
+            server_filter: None,
+            sort: default_sort("vm-instances"),
             parent_context: None,
             navigation_stack: Vec::new(),
             command_text: String::new(),
@@ -224,20 +579,46 @@ impl App {
             command_preview: None,
             project,
             zone,
+            region,
             available_projects,
             available_zones,
-            projects_selected: 0,
+            available_regions: Vec::new(),
+            project_picker: ProjectPicker::default(),
             zones_selected: 0,
+            location_kind: LocationKind::Zone,
+            location_query: String::new(),
+            location_searching: false,
+            locations_loading: false,
             pending_action: None,
+            action_form: None,
+            operations: Vec::new(),
+            operations_selected: 0,
+            next_operation_id: 0,
+            operation_tx,
+            operation_rx,
+            task_tx,
+            task_rx,
+            list_request_id: 0,
+            notification: None,
             loading: false,
+            list_progress: None,
             error: None,
-            describe_scroll: 0,
+            describe_view: DescribeView::default(),
             describe_data: None,
+            describe_loading: false,
+            describe_error: None,
+            describe_request_id: 0,
             last_refresh: std::time::Instant::now(),
+            refresh_requested: false,
             last_key_press: None,
+            yank_pending: false,
             warning_message: None,
+            info_message: None,
+            dialog_scroll: 0,
+            dialog_max_scroll: Cell::new(0),
+            readonly: readonly || config.context_readonly(),
+            cli_readonly: readonly,
             config,
-            readonly,
         }
     }
 
@@ -259,12 +640,13 @@ impl App {
         if !self.has_project() {
             return false;
         }
-        self.last_refresh.elapsed() >= std::time::Duration::from_secs(5)
+        self.refresh_requested || self.last_refresh.elapsed() >= std::time::Duration::from_secs(5)
     }
 
     /// Reset refresh timer
     pub fn mark_refreshed(&mut self) {
         self.last_refresh = std::time::Instant::now();
+        self.refresh_requested = false;
     }
 
     // =========================================================================
@@ -273,6 +655,12 @@ impl App {
 
     /// Get current resource definition
     pub fn current_resource(&self) -> Option<&'static ResourceDef> {
+        // Regional resources listed across all regions get a Location column
+        if self.client.all_regions() && self.parent_context.is_none() {
+            if let Some(resource) = get_all_regions_resource(&self.resource_key) {
+                return Some(resource);
+            }
+        }
         get_resource(&self.resource_key)
     }
 
@@ -283,9 +671,25 @@ impl App {
             .map(|s| s.to_string())
             .collect();
 
-        // Add projects and zones commands
+        // Add projects, zones and operations commands
         commands.push("projects".to_string());
         commands.push("zones".to_string());
+        commands.push("regions".to_string());
+        commands.push("operations".to_string());
+        commands.push("export".to_string());
+        commands.push("whoami".to_string());
+        commands.extend(
+            self.config
+                .context_names()
+                .into_iter()
+                .map(|name| format!("ctx {}", name)),
+        );
+        if self
+            .current_resource()
+            .is_some_and(|r| r.api.filter.is_some())
+        {
+            commands.push("filter".to_string());
+        }
 
         commands.sort();
         commands
@@ -295,41 +699,218 @@ impl App {
     // Data Fetching
     // =========================================================================
 
-    /// Fetch data for current resource
-    pub async fn refresh(&mut self) {
-        self.loading = true;
+    /// Fetch data for current resource in a background task
+    /// The result is applied by `process_task_messages`; a newer refresh makes
+    /// any earlier in-flight result stale
+    pub fn refresh(&mut self) {
+        self.list_request_id += 1;
         self.error = None;
+        self.list_progress = None;
+
+        let Some(resource) = get_resource(&self.resource_key) else {
+            self.loading = false;
+            self.show_error(&format!("Resource {} not found", self.resource_key));
+            self.mark_refreshed();
+            return;
+        };
 
-        if let Some(resource) = get_resource(&self.resource_key) {
-            // Get parent item if we're in a sub-resource context
-            let parent_item = self.parent_context.as_ref().map(|ctx| &ctx.item);
+        self.loading = true;
 
-            match list_resources(&self.client, resource, parent_item).await {
-                Ok(items) => {
-                    let prev_selected = self.selected;
-                    self.items = items;
-                    self.apply_filter();
+        // Get parent item if we're in a sub-resource context
+        let parent_item = self.parent_context.as_ref().map(|ctx| ctx.item.clone());
+        let server_filter = self.server_filter.clone();
+        let client = self.client.clone();
+        let request_id = self.list_request_id;
+        let tx = self.task_tx.clone();
+
+        tokio::spawn(async move {
+            let progress_tx = tx.clone();
+            let mut on_page = move |progress| {
+                let _ = progress_tx.send(TaskMessage::ListProgress {
+                    request_id,
+                    progress,
+                });
+            };
+            let result = list_resources(
+                &client,
+                resource,
+                parent_item.as_ref(),
+                server_filter.as_deref(),
+                &mut on_page,
+            )
+            .await
+            .map_err(|e| e.to_string());
+            let _ = tx.send(TaskMessage::Listed { request_id, result });
+        });
+    }
 
-                    // Try to keep the same selection index
-                    if prev_selected < self.filtered_items.len() {
-                        self.selected = prev_selected;
-                    } else {
+    /// Apply results sent by background requests (non-blocking)
+    pub fn process_task_messages(&mut self) {
+        while let Ok(message) = self.task_rx.try_recv() {
+            self.apply_task_message(message);
+        }
+    }
+
+    fn apply_task_message(&mut self, message: TaskMessage) {
+        match message {
+            TaskMessage::ListProgress {
+                request_id,
+                progress,
+            } => {
+                if request_id == self.list_request_id {
+                    self.list_progress = Some(progress);
+                }
+            }
+            TaskMessage::Listed { request_id, result } => {
+                if request_id != self.list_request_id {
+                    tracing::debug!("Discarding stale list response {}", request_id);
+                    return;
+                }
+                self.loading = false;
+                self.mark_refreshed();
+
+                match result {
+                    Ok(items) => {
+                        let prev_selected = self.selected;
+                        self.items = items;
+                        self.apply_filter();
+
+                        // Forget marks of items that are gone
+                        let keys: HashSet<String> =
+                            self.items.iter().map(|i| self.item_key(i)).collect();
+                        self.marked.retain(|k| keys.contains(k));
+
+                        // Try to keep the same selection index
+                        if prev_selected < self.filtered_items.len() {
+                            self.selected = prev_selected;
+                        } else {
+                            self.selected = 0;
+                        }
+                    }
+                    Err(e) => {
+                        self.report_error(&e);
+                        self.items.clear();
+                        self.filtered_items.clear();
                         self.selected = 0;
                     }
                 }
+            }
+            TaskMessage::Described { request_id, result } => {
+                // Ignore responses for a describe view that was already closed
+                if request_id != self.describe_request_id || self.mode != Mode::Describe {
+                    tracing::debug!("Discarding stale describe response {}", request_id);
+                    return;
+                }
+                self.describe_loading = false;
+                match result {
+                    Ok(full) => {
+                        self.describe_view.clamp(&full);
+                        self.describe_data = Some(full);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to fetch full resource: {}", e);
+                        self.describe_error = Some(e);
+                    }
+                }
+            }
+            TaskMessage::BulkFinished {
+                description,
+                action_name,
+                resource_key,
+                api_base,
+                results,
+            } => self.apply_bulk_results(description, action_name, resource_key, api_base, results),
+            TaskMessage::ActionFinished {
+                description,
+                resource_key,
+                api_base,
+                result,
+            } => match result {
+                Ok(response) => {
+                    // Track the operation if the API returned one
+                    if let Some(op) = detect_operation(&api_base, &response) {
+                        let status = parse_status(op.kind, &response);
+                        self.track_operation(description, resource_key.clone(), op, status);
+                    } else {
+                        self.notify(&format!("{} done", description), false);
+                    }
+                    // Action succeeded - refresh to see updated state
+                    if resource_key == self.resource_key {
+                        self.refresh_requested = true;
+                    }
+                }
                 Err(e) => {
-                    self.show_error(&e.to_string());
-                    self.items.clear();
-                    self.filtered_items.clear();
-                    self.selected = 0;
+                    self.report_error(&format!("{} failed: {}", description, e));
+                }
+            },
+            TaskMessage::Whoami { result } => match result {
+                Ok(identity) => self.show_info(&whoami_message(&identity, chrono::Utc::now())),
+                Err(e) => self.show_error(&format!("Authentication failed: {}", e)),
+            },
+            TaskMessage::ProjectsListed { result } => match result {
+                Ok(projects) => {
+                    self.available_projects =
+                        with_known_projects(projects, &self.project, &self.config)
+                }
+                Err(e) => self.notify(&format!("Failed to list projects: {}", e), true),
+            },
+            TaskMessage::ContainersListed { result } => {
+                self.project_picker.loading = false;
+                self.project_picker.containers_loaded = true;
+                match result {
+                    Ok(containers) => {
+                        self.project_picker.containers = containers;
+                        if self.mode == Mode::Projects {
+                            self.select_current_project();
+                        }
+                    }
+                    Err(e) => self.notify(&format!("Failed to list folders: {}", e), true),
+                }
+            }
+            TaskMessage::LocationsListed {
+                project,
+                kind,
+                result,
+            } => {
+                // The project changed while loading
+                if project != self.project {
+                    return;
+                }
+                if kind == self.location_kind {
+                    self.locations_loading = false;
+                }
+                match result {
+                    Ok(locations) => {
+                        match kind {
+                            LocationKind::Zone => self.available_zones = locations,
+                            LocationKind::Region => self.available_regions = locations,
+                        }
+                        if self.mode == Mode::Zones && kind == self.location_kind {
+                            self.select_current_location();
+                        }
+                    }
+                    Err(e) => {
+                        self.set_fallback_locations(kind);
+                        self.notify(
+                            &format!(
+                                "Failed to list {}, showing a built-in list: {}",
+                                kind.plural(),
+                                e
+                            ),
+                            true,
+                        )
+                    }
                 }
             }
-        } else {
-            self.show_error(&format!("Resource {} not found", self.resource_key));
         }
+    }
 
-        self.loading = false;
-        self.mark_refreshed();
+    /// Clear the current list while the next resource loads
+    fn clear_items(&mut self) {
+        self.items.clear();
+        self.filtered_items.clear();
+        self.marked.clear();
+        self.list_progress = None;
     }
 
     // =========================================================================
@@ -338,36 +919,216 @@ impl App {
 
     /// Apply text filter to items
     pub fn apply_filter(&mut self) {
-        let filter = self.filter_text.to_lowercase();
+        self.filter = Filter::parse(&self.filter_text);
 
-        if filter.is_empty() {
+        if self.filter.is_empty() {
             self.filtered_items = self.items.clone();
         } else {
             let resource = self.current_resource();
             self.filtered_items = self
                 .items
                 .iter()
-                .filter(|item| {
-                    // Search in name field and id field
-                    if let Some(res) = resource {
-                        let name = extract_json_value(item, &res.name_field).to_lowercase();
-                        let id = extract_json_value(item, &res.id_field).to_lowercase();
-                        name.contains(&filter) || id.contains(&filter)
-                    } else {
-                        // Fallback: search in JSON string
-                        item.to_string().to_lowercase().contains(&filter)
-                    }
-                })
+                .filter(|item| self.filter.matches(resource, item))
                 .cloned()
                 .collect();
         }
 
+        if let Some(sort) = self.sort {
+            if let Some(column) = self
+                .current_resource()
+                .and_then(|r| r.columns.get(sort.column))
+            {
+                sort_items(&mut self.filtered_items, column, sort.descending);
+            }
+        }
+
         // Adjust selection
         if self.selected >= self.filtered_items.len() && !self.filtered_items.is_empty() {
             self.selected = self.filtered_items.len() - 1;
         }
     }
 
+    // =========================================================================
+    // Marking
+    // =========================================================================
+
+    /// Key identifying an item across refreshes, its id or else its name
+    pub fn item_key(&self, item: &Value) -> String {
+        let Some(resource) = self.current_resource() else {
+            return item.to_string();
+        };
+        let id = extract_json_value(item, &resource.id_field);
+        if id != "-" {
+            id
+        } else {
+            extract_json_value(item, &resource.name_field)
+        }
+    }
+
+    pub fn is_marked(&self, item: &Value) -> bool {
+        !self.marked.is_empty() && self.marked.contains(&self.item_key(item))
+    }
+
+    /// Marked items passing the filter, those an action runs on
+    pub fn marked_shown(&self) -> usize {
+        if self.marked.is_empty() {
+            return 0;
+        }
+        self.filtered_items
+            .iter()
+            .filter(|i| self.is_marked(i))
+            .count()
+    }
+
+    /// Mark or unmark the selected item and move to the next one
+    pub fn toggle_mark(&mut self) {
+        let Some(item) = self.selected_item() else {
+            return;
+        };
+        let key = self.item_key(item);
+        if !self.marked.remove(&key) {
+            self.marked.insert(key);
+        }
+        self.next();
+    }
+
+    /// Mark every item passing the filter, or unmark them if all are marked
+    pub fn toggle_mark_all(&mut self) {
+        let keys: Vec<String> = self
+            .filtered_items
+            .iter()
+            .map(|i| self.item_key(i))
+            .collect();
+        if keys.iter().all(|k| self.marked.contains(k)) {
+            for key in &keys {
+                self.marked.remove(key);
+            }
+        } else {
+            self.marked.extend(keys);
+        }
+    }
+
+    /// Items an action applies to: the marked items passing the filter, or else the
+    /// selected one; marks hidden by the filter are kept for when it is cleared
+    fn action_targets(&self) -> Vec<Value> {
+        if self.marked.is_empty() {
+            return self.selected_item().cloned().into_iter().collect();
+        }
+        self.filtered_items
+            .iter()
+            .filter(|i| self.is_marked(i))
+            .cloned()
+            .collect()
+    }
+
+    // =========================================================================
+    // Sorting
+    // =========================================================================
+
+    /// Move the sort to the next or previous column
+    /// Cycling past either end goes back to the API order
+    pub fn cycle_sort_column(&mut self, forward: bool) {
+        let Some(count) = self.current_resource().map(|r| r.columns.len()) else {
+            return;
+        };
+        if count == 0 {
+            return;
+        }
+        let descending = self.sort.is_some_and(|s| s.descending);
+        let column = match (self.sort, forward) {
+            (None, true) => Some(0),
+            (None, false) => Some(count - 1),
+            (Some(s), true) => (s.column + 1 < count).then_some(s.column + 1),
+            (Some(s), false) => s.column.checked_sub(1),
+        };
+        self.set_sort(column.map(|column| SortState { column, descending }));
+    }
+
+    /// Flip between ascending and descending, sorting by the first column if unsorted
+    pub fn toggle_sort_order(&mut self) {
+        let sort = match self.sort {
+            Some(s) => SortState {
+                descending: !s.descending,
+                ..s
+            },
+            None => SortState {
+                column: 0,
+                descending: false,
+            },
+        };
+        self.set_sort(Some(sort));
+    }
+
+    /// Re-sort the list, keeping the selected item selected
+    fn set_sort(&mut self, sort: Option<SortState>) {
+        let selected = self.selected_item().cloned();
+        self.sort = sort;
+        self.apply_filter();
+        if let Some(index) = selected.and_then(|s| self.filtered_items.iter().position(|i| *i == s))
+        {
+            self.selected = index;
+        }
+    }
+
+    /// Set the expression sent to the list API and reload, an empty one restores the default
+    pub fn set_server_filter(&mut self, expr: &str) {
+        let Some(resource) = self.current_resource() else {
+            return;
+        };
+        let Some(def) = &resource.api.filter else {
+            self.show_error(&format!(
+                "{} does not support server-side filtering, use / to filter the loaded list",
+                resource.display_name
+            ));
+            return;
+        };
+
+        if expr.trim().is_empty() {
+            self.server_filter = None;
+        } else {
+            match def.validate(expr) {
+                Ok(expr) => self.server_filter = Some(expr),
+                Err(e) => {
+                    self.show_error(&format!("Invalid filter: {}", e));
+                    return;
+                }
+            }
+        }
+
+        self.selected = 0;
+        self.clear_items();
+        self.refresh();
+    }
+
+    /// Server-side filter in effect, including the definition's default
+    pub fn active_server_filter(&self) -> Option<&str> {
+        let def = self.current_resource()?.api.filter.as_ref()?;
+        self.server_filter
+            .as_deref()
+            .or(def.default.as_deref())
+            .filter(|e| !e.is_empty())
+    }
+
+    /// Syntax hint while a :filter or :export command is typed
+    pub fn command_hint(&self) -> Option<String> {
+        if self.command_text.starts_with("export") {
+            return Some("export: <path> [--format csv|json|yaml|md] [--raw]".to_string());
+        }
+        if self.command_text.starts_with("zone ") {
+            return Some("zone: <zone> (e.g., europe-west1-b) or all".to_string());
+        }
+        if self.command_text.starts_with("region") {
+            return Some("region: <region> (e.g., europe-west1) or all".to_string());
+        }
+        if !self.command_text.starts_with("filter") {
+            return None;
+        }
+        match self.current_resource()?.api.filter.as_ref() {
+            Some(def) => Some(format!("filter: {}", def.hint())),
+            None => Some("filter: not supported by this resource".to_string()),
+        }
+    }
+
     #[allow(dead_code)]
     pub fn toggle_filter(&mut self) {
         self.filter_active = !self.filter_active;
@@ -387,40 +1148,25 @@ impl App {
         self.filtered_items.get(self.selected)
     }
 
-    pub fn selected_item_json(&self) -> Option<String> {
-        // Use describe_data if available (full details), otherwise fall back to list data
-        if let Some(ref data) = self.describe_data {
-            return Some(serde_json::to_string_pretty(data).unwrap_or_default());
-        }
-        self.selected_item()
-            .map(|item| serde_json::to_string_pretty(item).unwrap_or_default())
-    }
-
-    /// Get the number of lines in the describe content
-    pub fn describe_line_count(&self) -> usize {
-        self.selected_item_json()
-            .map(|s| s.lines().count())
-            .unwrap_or(0)
-    }
-
-    /// Scroll describe view to bottom
-    pub fn describe_scroll_to_bottom(&mut self, visible_lines: usize) {
-        let total = self.describe_line_count();
-        self.describe_scroll = total.saturating_sub(visible_lines);
-    }
-
     pub fn next(&mut self) {
         match self.mode {
             Mode::Projects => {
-                if !self.available_projects.is_empty() {
-                    self.projects_selected =
-                        (self.projects_selected + 1).min(self.available_projects.len() - 1);
+                let count = self.project_rows().len();
+                if count > 0 {
+                    self.project_picker.selected =
+                        (self.project_picker.selected + 1).min(count - 1);
                 }
             }
             Mode::Zones => {
-                if !self.available_zones.is_empty() {
-                    self.zones_selected =
-                        (self.zones_selected + 1).min(self.available_zones.len() - 1);
+                let count = self.filtered_locations().len();
+                if count > 0 {
+                    self.zones_selected = (self.zones_selected + 1).min(count - 1);
+                }
+            }
+            Mode::Operations => {
+                if !self.operations.is_empty() {
+                    self.operations_selected =
+                        (self.operations_selected + 1).min(self.operations.len() - 1);
                 }
             }
             _ => {
@@ -434,11 +1180,14 @@ impl App {
     pub fn previous(&mut self) {
         match self.mode {
             Mode::Projects => {
-                self.projects_selected = self.projects_selected.saturating_sub(1);
+                self.project_picker.selected = self.project_picker.selected.saturating_sub(1);
             }
             Mode::Zones => {
                 self.zones_selected = self.zones_selected.saturating_sub(1);
             }
+            Mode::Operations => {
+                self.operations_selected = self.operations_selected.saturating_sub(1);
+            }
             _ => {
                 self.selected = self.selected.saturating_sub(1);
             }
@@ -447,8 +1196,9 @@ impl App {
 
     pub fn go_to_top(&mut self) {
         match self.mode {
-            Mode::Projects => self.projects_selected = 0,
+            Mode::Projects => self.project_picker.selected = 0,
             Mode::Zones => self.zones_selected = 0,
+            Mode::Operations => self.operations_selected = 0,
             _ => self.selected = 0,
         }
     }
@@ -456,13 +1206,20 @@ impl App {
     pub fn go_to_bottom(&mut self) {
         match self.mode {
             Mode::Projects => {
-                if !self.available_projects.is_empty() {
-                    self.projects_selected = self.available_projects.len() - 1;
+                let count = self.project_rows().len();
+                if count > 0 {
+                    self.project_picker.selected = count - 1;
                 }
             }
             Mode::Zones => {
-                if !self.available_zones.is_empty() {
-                    self.zones_selected = self.available_zones.len() - 1;
+                let count = self.filtered_locations().len();
+                if count > 0 {
+                    self.zones_selected = count - 1;
+                }
+            }
+            Mode::Operations => {
+                if !self.operations.is_empty() {
+                    self.operations_selected = self.operations.len() - 1;
                 }
             }
             _ => {
@@ -477,15 +1234,16 @@ impl App {
     pub fn page_down(&mut self, page_size: usize) {
         match self.mode {
             Mode::Projects => {
-                if !self.available_projects.is_empty() {
-                    self.projects_selected =
-                        (self.projects_selected + page_size).min(self.available_projects.len() - 1);
+                let count = self.project_rows().len();
+                if count > 0 {
+                    self.project_picker.selected =
+                        (self.project_picker.selected + page_size).min(count - 1);
                 }
             }
             Mode::Zones => {
-                if !self.available_zones.is_empty() {
-                    self.zones_selected =
-                        (self.zones_selected + page_size).min(self.available_zones.len() - 1);
+                let count = self.filtered_locations().len();
+                if count > 0 {
+                    self.zones_selected = (self.zones_selected + page_size).min(count - 1);
                 }
             }
             _ => {
@@ -500,7 +1258,8 @@ impl App {
     pub fn page_up(&mut self, page_size: usize) {
         match self.mode {
             Mode::Projects => {
-                self.projects_selected = self.projects_selected.saturating_sub(page_size);
+                self.project_picker.selected =
+                    self.project_picker.selected.saturating_sub(page_size);
             }
             Mode::Zones => {
                 self.zones_selected = self.zones_selected.saturating_sub(page_size);
@@ -592,54 +1351,436 @@ impl App {
         }
 
         self.mode = Mode::Describe;
-        self.describe_scroll = 0;
+        // Start at the top, keeping the chosen JSON/YAML format
+        self.describe_view = DescribeView {
+            format: self.describe_view.format,
+            viewport: self.describe_view.viewport.clone(),
+            ..DescribeView::default()
+        };
         self.describe_data = self.selected_item().cloned();
-    }
+        self.describe_error = None;
+        self.describe_request_id += 1;
 
-    /// Enter confirmation mode for an action
-    #[allow(dead_code)]
-    pub fn enter_confirm_mode(&mut self, pending: PendingAction) {
-        self.pending_action = Some(pending);
-        self.mode = Mode::Confirm;
+        // Fetch the full resource, the list item is shown until it arrives
+        let Some(resource) = self.current_resource().filter(|r| r.get.is_some()) else {
+            self.describe_loading = false;
+            return;
+        };
+        let Some(item) = self.describe_data.clone() else {
+            return;
+        };
+        self.describe_loading = true;
+
+        let parent_item = self.parent_context.as_ref().map(|ctx| ctx.item.clone());
+        let client = self.client.clone();
+        let request_id = self.describe_request_id;
+        let tx = self.task_tx.clone();
+
+        tokio::spawn(async move {
+            let result = describe_resource(&client, resource, &item, parent_item.as_ref())
+                .await
+                .map(|full| full.unwrap_or(item))
+                .map_err(|e| e.to_string());
+            let _ = tx.send(TaskMessage::Described { request_id, result });
+        });
     }
 
-    /// Show a warning modal with OK button
-    pub fn show_warning(&mut self, message: &str) {
-        self.warning_message = Some(message.to_string());
-        self.error = None;
-        self.mode = Mode::Warning;
+    /// Copy the JSON pointer of the selected describe node to the clipboard
+    pub fn copy_describe_pointer(&mut self) {
+        let Some(data) = &self.describe_data else {
+            return;
+        };
+        let Some(pointer) = self.describe_view.selected_pointer(data) else {
+            return;
+        };
+        let shown = if pointer.is_empty() {
+            "(root)".to_string()
+        } else {
+            pointer.clone()
+        };
+        self.copy_to_clipboard(&pointer, &shown);
     }
 
-    /// Show an error modal with OK button
-    pub fn show_error(&mut self, message: &str) {
-        self.error = Some(message.to_string());
-        self.warning_message = None;
-        self.mode = Mode::Warning;
+    /// Copy the whole describe document in the format it is shown in
+    pub fn copy_describe_document(&mut self) {
+        let Some(data) = &self.describe_data else {
+            return;
+        };
+        let format = self.describe_view.format;
+        let text = match format {
+            DocFormat::Json => serde_json::to_string_pretty(data).map_err(anyhow::Error::from),
+            DocFormat::Yaml => serde_yaml::to_string(data).map_err(anyhow::Error::from),
+        };
+        match text {
+            Ok(text) => self.copy_to_clipboard(&text, &format!("document as {}", format.label())),
+            Err(e) => self.notify(&format!("Copy failed: {}", e), true),
+        }
     }
 
-    pub fn enter_projects_mode(&mut self) {
-        self.projects_selected = self
-            .available_projects
-            .iter()
-            .position(|p| p == &self.project)
-            .unwrap_or(0);
+    /// Copy part of the selected item, or of every marked item one per line
+    pub fn yank(&mut self, target: YankTarget) {
+        self.yank_pending = false;
+        let Some(resource) = self.current_resource() else {
+            return;
+        };
+        let items = self.action_targets();
+        let Some(first) = items.first() else {
+            return;
+        };
+
+        let label = match target {
+            YankTarget::Name => "name".to_string(),
+            YankTarget::Id => "id".to_string(),
+            YankTarget::Link => "link".to_string(),
+            YankTarget::Column(i) => match resource.columns.get(i) {
+                Some(col) => col.header.to_lowercase(),
+                None => return,
+            },
+            YankTarget::Json => "JSON".to_string(),
+        };
+
+        let text = if target == YankTarget::Json {
+            let value = match items.as_slice() {
+                [item] => item.clone(),
+                _ => Value::Array(items.clone()),
+            };
+            serde_json::to_string_pretty(&value).unwrap_or_default()
+        } else {
+            let values: Vec<String> = items
+                .iter()
+                .filter_map(|item| self.yank_value(resource, item, target))
+                .collect();
+            if values.is_empty() {
+                self.notify(&format!("No {} to copy", label), true);
+                return;
+            }
+            values.join("\n")
+        };
+
+        let description = match items.len() {
+            1 if target == YankTarget::Json => format!(
+                "{} as JSON",
+                extract_json_value(first, &resource.name_field)
+            ),
+            1 => text.clone(),
+            n => format!("{} of {} items", label, n),
+        };
+        self.copy_to_clipboard(&text, &description);
+    }
+
+    /// Text of a yank target for one item, None if the item has no such value
+    fn yank_value(
+        &self,
+        resource: &ResourceDef,
+        item: &Value,
+        target: YankTarget,
+    ) -> Option<String> {
+        let value = match target {
+            YankTarget::Name => extract_json_value(item, &resource.name_field),
+            YankTarget::Id => extract_json_value(item, &resource.id_field),
+            YankTarget::Link => {
+                if let Some(link) = item.get("selfLink").and_then(Value::as_str) {
+                    return Some(link.to_string());
+                }
+                let parent_item = self.parent_context.as_ref().map(|ctx| &ctx.item);
+                return item_url(&self.client, resource, item, parent_item);
+            }
+            YankTarget::Column(i) => extract_json_value(item, &resource.columns.get(i)?.json_path),
+            YankTarget::Json => serde_json::to_string_pretty(item).ok()?,
+        };
+        (value != "-").then_some(value)
+    }
+
+    /// Open the selected item's Cloud Console page in the browser
+    /// Without a local browser (e.g., over SSH) the URL is copied instead
+    pub fn open_in_console(&mut self) {
+        let Some(resource) = self.current_resource() else {
+            return;
+        };
+        let Some(item) = self.selected_item() else {
+            return;
+        };
+        let parent_item = self.parent_context.as_ref().map(|ctx| &ctx.item);
+        let Some(url) = console_url(&self.client, resource, item, parent_item) else {
+            self.notify(
+                &format!("{} has no console page", resource.display_name),
+                true,
+            );
+            return;
+        };
+
+        if browser_available() {
+            match open::that_detached(&url) {
+                Ok(()) => {
+                    self.notify(&format!("Opened {}", url), false);
+                    return;
+                }
+                Err(e) => tracing::warn!("Failed to open browser: {}", e),
+            }
+        }
+        self.copy_to_clipboard(&url, &url);
+    }
+
+    /// Copy text to the clipboard and report it in the crumb
+    fn copy_to_clipboard(&mut self, text: &str, description: &str) {
+        match clipboard::copy(text, self.config.clipboard_command.as_deref()) {
+            Ok(()) => self.notify(&format!("Copied {}", description), false),
+            Err(e) => self.notify(&format!("Copy failed: {}", e), true),
+        }
+    }
+
+    /// Enter confirmation mode for an action
+    #[allow(dead_code)]
+    pub fn enter_confirm_mode(&mut self, pending: PendingAction) {
+        self.pending_action = Some(pending);
+        self.mode = Mode::Confirm;
+    }
+
+    /// Show a warning modal with OK button
+    pub fn show_warning(&mut self, message: &str) {
+        self.warning_message = Some(message.to_string());
+        self.error = None;
+        self.info_message = None;
+        self.dialog_scroll = 0;
+        self.mode = Mode::Warning;
+    }
+
+    /// Show an error modal with OK button
+    pub fn show_error(&mut self, message: &str) {
+        self.error = Some(message.to_string());
+        self.warning_message = None;
+        self.info_message = None;
+        self.dialog_scroll = 0;
+        self.mode = Mode::Warning;
+    }
+
+    /// Show an information modal with OK button
+    pub fn show_info(&mut self, message: &str) {
+        self.info_message = Some(message.to_string());
+        self.warning_message = None;
+        self.error = None;
+        self.dialog_scroll = 0;
+        self.mode = Mode::Warning;
+    }
+
+    /// Scroll the message of the confirm or warning dialog by one line
+    pub fn scroll_dialog(&mut self, down: bool) {
+        self.dialog_scroll = if down {
+            (self.dialog_scroll + 1).min(self.dialog_max_scroll.get())
+        } else {
+            self.dialog_scroll.saturating_sub(1)
+        };
+    }
+
+    pub fn enter_projects_mode(&mut self) {
+        self.project_picker.query.clear();
         self.mode = Mode::Projects;
+        self.select_current_project();
+    }
+
+    /// Rows of the project picker
+    pub fn project_rows(&self) -> Vec<PickerRow> {
+        self.project_picker.rows(
+            &self.available_projects,
+            &self.config.pinned_projects,
+            &self.config.recent_projects,
+        )
+    }
+
+    /// Project of the selected picker row, None on a folder or organization
+    pub fn picker_project(&self) -> Option<&ProjectInfo> {
+        match self.project_rows().get(self.project_picker.selected)? {
+            PickerRow::Project { index, .. } => self.available_projects.get(*index),
+            PickerRow::Container { .. } => None,
+        }
+    }
+
+    fn select_current_project(&mut self) {
+        self.project_picker.selected = self
+            .project_rows()
+            .iter()
+            .position(|row| {
+                matches!(row, PickerRow::Project { index, .. }
+                    if self.available_projects[*index].project_id == self.project)
+            })
+            .unwrap_or(0);
+    }
+
+    /// Type into the project search, selecting the best match
+    pub fn push_project_query(&mut self, c: char) {
+        self.project_picker.query.push(c);
+        self.project_picker.selected = 0;
+    }
+
+    pub fn pop_project_query(&mut self) {
+        self.project_picker.query.pop();
+        self.project_picker.selected = 0;
+    }
+
+    /// Switch between the flat list and the organization tree
+    pub fn toggle_project_view(&mut self) {
+        self.project_picker.view = match self.project_picker.view {
+            ProjectView::List => ProjectView::Tree,
+            ProjectView::Tree => ProjectView::List,
+        };
+        if self.project_picker.view == ProjectView::Tree
+            && !self.project_picker.containers_loaded
+            && !self.project_picker.loading
+        {
+            self.load_containers();
+        }
+        self.select_current_project();
+    }
+
+    /// Fetch organizations and folders in a background task
+    fn load_containers(&mut self) {
+        self.project_picker.loading = true;
+        let client = self.client.clone();
+        let tx = self.task_tx.clone();
+
+        tokio::spawn(async move {
+            let result = client.list_containers().await.map_err(|e| e.to_string());
+            let _ = tx.send(TaskMessage::ContainersListed { result });
+        });
+    }
+
+    /// Pin the selected project to the top of the list, or unpin it
+    pub fn toggle_pin_project(&mut self) {
+        let Some(project) = self.picker_project().map(|p| p.project_id.clone()) else {
+            return;
+        };
+        match self.config.toggle_pinned_project(&project) {
+            Ok(true) => self.notify(&format!("Pinned {}", project), false),
+            Ok(false) => self.notify(&format!("Unpinned {}", project), false),
+            Err(e) => self.notify(&format!("Failed to save pinned projects: {}", e), true),
+        }
     }
 
     pub fn enter_zones_mode(&mut self) {
+        self.open_location_picker(LocationKind::Zone);
+    }
+
+    pub fn enter_regions_mode(&mut self) {
+        self.open_location_picker(LocationKind::Region);
+    }
+
+    /// Open the zone picker on zones or regions, fetching them on first use
+    fn open_location_picker(&mut self, kind: LocationKind) {
+        self.location_kind = kind;
+        self.location_query.clear();
+        self.location_searching = false;
+        self.mode = Mode::Zones;
+        if self.picker_locations().is_empty() {
+            self.load_locations(kind);
+        }
+        self.select_current_location();
+    }
+
+    /// Fetch the zones or regions of the project in a background task
+    fn load_locations(&mut self, kind: LocationKind) {
+        if !self.has_project() {
+            self.set_fallback_locations(kind);
+            return;
+        }
+        self.locations_loading = true;
+        let client = self.client.clone();
+        let tx = self.task_tx.clone();
+
+        tokio::spawn(async move {
+            let result = match kind {
+                LocationKind::Zone => client.list_zones().await,
+                LocationKind::Region => client.list_regions().await,
+            };
+            let _ = tx.send(TaskMessage::LocationsListed {
+                project: client.project,
+                kind,
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
+    /// Zones or regions listed by the picker
+    pub fn picker_locations(&self) -> &[ComputeLocation] {
+        match self.location_kind {
+            LocationKind::Zone => &self.available_zones,
+            LocationKind::Region => &self.available_regions,
+        }
+    }
+
+    /// Picker entries matching the search, best match first
+    pub fn filtered_locations(&self) -> Vec<&ComputeLocation> {
+        fuzzy::filter(&self.location_query, self.picker_locations(), |l| &l.name)
+    }
+
+    /// Zone or region in use, highlighted in the picker
+    pub fn current_location(&self) -> &str {
+        match self.location_kind {
+            LocationKind::Zone => &self.zone,
+            LocationKind::Region => &self.region,
+        }
+    }
+
+    /// Fill the picker with the built-in and favourite zones (or their regions)
+    fn set_fallback_locations(&mut self, kind: LocationKind) {
+        let mut zones: Vec<String> = FALLBACK_ZONES.iter().map(|z| z.to_string()).collect();
+        zones.extend(self.config.effective_favorite_zones());
+        let mut names: Vec<String> = match kind {
+            LocationKind::Zone => zones,
+            LocationKind::Region => zones.iter().map(|z| derive_region(z)).collect(),
+        };
+        names.sort();
+        names.dedup();
+
+        // The status is unknown without the API
+        let locations = names
+            .into_iter()
+            .map(|name| ComputeLocation {
+                name,
+                status: String::new(),
+            })
+            .collect();
+        match kind {
+            LocationKind::Zone => self.available_zones = locations,
+            LocationKind::Region => self.available_regions = locations,
+        }
+        if self.mode == Mode::Zones && kind == self.location_kind {
+            self.select_current_location();
+        }
+    }
+
+    fn select_current_location(&mut self) {
         self.zones_selected = self
-            .available_zones
+            .filtered_locations()
             .iter()
-            .position(|z| z == &self.zone)
+            .position(|l| l.name == self.current_location())
             .unwrap_or(0);
-        self.mode = Mode::Zones;
+    }
+
+    /// Type into the picker search, selecting the best match
+    pub fn push_location_query(&mut self, c: char) {
+        self.location_query.push(c);
+        self.zones_selected = 0;
+    }
+
+    pub fn pop_location_query(&mut self) {
+        self.location_query.pop();
+        self.zones_selected = 0;
+    }
+
+    pub fn enter_operations_mode(&mut self) {
+        self.operations_selected = 0;
+        self.mode = Mode::Operations;
     }
 
     pub fn exit_mode(&mut self) {
         self.mode = Mode::Normal;
         self.pending_action = None;
+        self.action_form = None;
         self.describe_data = None;
+        self.describe_loading = false;
+        self.describe_error = None;
         self.warning_message = None;
+        self.info_message = None;
         self.error = None;
     }
 
@@ -648,7 +1789,7 @@ impl App {
     // =========================================================================
 
     /// Navigate to a resource (top-level)
-    pub async fn navigate_to_resource(&mut self, resource_key: &str) {
+    pub fn navigate_to_resource(&mut self, resource_key: &str) {
         if get_resource(resource_key).is_none() {
             self.error = Some(format!("Unknown resource: {}", resource_key));
             return;
@@ -658,16 +1799,19 @@ impl App {
         self.parent_context = None;
         self.navigation_stack.clear();
         self.resource_key = resource_key.to_string();
+        self.sort = default_sort(resource_key);
+        self.server_filter = None;
         self.selected = 0;
         self.filter_text.clear();
         self.filter_active = false;
         self.mode = Mode::Normal;
+        self.clear_items();
 
-        self.refresh().await;
+        self.refresh();
     }
 
     /// Navigate to sub-resource with parent context
-    pub async fn navigate_to_sub_resource(&mut self, sub_resource_key: &str) {
+    pub fn navigate_to_sub_resource(&mut self, sub_resource_key: &str) {
         let Some(selected_item) = self.selected_item().cloned() else {
             return;
         };
@@ -713,26 +1857,32 @@ impl App {
 
         // Navigate
         self.resource_key = sub_resource_key.to_string();
+        self.sort = default_sort(sub_resource_key);
+        self.server_filter = None;
         self.selected = 0;
         self.filter_text.clear();
         self.filter_active = false;
+        self.clear_items();
 
-        self.refresh().await;
+        self.refresh();
     }
 
     /// Navigate back to parent resource
-    pub async fn navigate_back(&mut self) {
+    pub fn navigate_back(&mut self) {
         if let Some(parent) = self.parent_context.take() {
             // Pop from navigation stack if available
             self.parent_context = self.navigation_stack.pop();
 
             // Navigate to parent resource
             self.resource_key = parent.resource_key;
+            self.sort = default_sort(&self.resource_key);
+            self.server_filter = None;
             self.selected = 0;
             self.filter_text.clear();
             self.filter_active = false;
+            self.clear_items();
 
-            self.refresh().await;
+            self.refresh();
         }
     }
 
@@ -752,11 +1902,66 @@ impl App {
         path
     }
 
+    /// Write the filtered items to a file, `args` as in `ExportRequest::parse`
+    pub fn export(&mut self, args: &str) {
+        let result = ExportRequest::parse(args).and_then(|request| {
+            let resource = self
+                .current_resource()
+                .ok_or_else(|| anyhow!("Nothing to export"))?;
+            let text = render_export(
+                resource,
+                &self.filtered_items,
+                request.format,
+                request.raw,
+                &self.export_meta(resource),
+            )?;
+            std::fs::write(&request.path, text)
+                .with_context(|| format!("Cannot write {}", request.path.display()))?;
+            Ok(request.path)
+        });
+
+        match result {
+            Ok(path) => self.notify(
+                &format!(
+                    "Exported {} items to {}",
+                    self.filtered_items.len(),
+                    path.display()
+                ),
+                false,
+            ),
+            Err(e) => self.show_error(&format!("Export failed: {:#}", e)),
+        }
+    }
+
+    fn export_meta(&self, resource: &ResourceDef) -> ExportMeta {
+        let mut filters = Vec::new();
+        if !self.filter_text.is_empty() {
+            filters.push(self.filter_text.clone());
+        }
+        if let Some(expr) = self.active_server_filter() {
+            filters.push(format!("<{}>", expr));
+        }
+        ExportMeta {
+            resource: resource.display_name.clone(),
+            breadcrumb: self.get_breadcrumb(),
+            project: self.project.clone(),
+            zone: self.zone.clone(),
+            region: region_label(&self.region).to_string(),
+            filters,
+            exported_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        }
+    }
+
     // =========================================================================
     // Zone/Project Switching
     // =========================================================================
 
-    pub async fn switch_zone(&mut self, zone: &str) {
+    pub fn switch_zone(&mut self, zone: &str) {
+        if zone == ALL_ZONES {
+            self.set_all_zones(true);
+            return;
+        }
+        self.client.all_zones = false;
         self.zone = zone.to_string();
         self.client.set_zone(zone);
         // Save to config
@@ -765,38 +1970,177 @@ impl App {
         }
     }
 
-    pub async fn switch_project(&mut self, project: &str) {
+    /// List zonal resources across all zones, keeping the zone for sub-resources
+    pub fn set_all_zones(&mut self, all_zones: bool) {
+        self.client.all_zones = all_zones;
+    }
+
+    /// Toggle the all-zones view and reload the list
+    pub fn toggle_all_zones(&mut self) {
+        self.set_all_zones(!self.client.all_zones);
+        self.refresh();
+    }
+
+    /// Zone shown in the header, "all" in all-zones mode
+    pub fn zone_label(&self) -> &str {
+        if self.client.all_zones {
+            ALL_ZONES
+        } else {
+            &self.zone
+        }
+    }
+
+    /// Switch the region of regional resources, "all" lists every region
+    pub fn switch_region(&mut self, region: &str) {
+        self.region = parse_region(region);
+        self.client.region = self.region.clone();
+        if let Err(e) = self.config.set_region(region_label(&self.region)) {
+            tracing::warn!("Failed to save region to config: {}", e);
+        }
+    }
+
+    pub fn switch_project(&mut self, project: &str) {
         self.project = project.to_string();
         self.client.project = project.to_string();
+        // Zones and regions are per project, the client caches them
+        self.available_zones.clear();
+        self.available_regions.clear();
         // Save to config
         if let Err(e) = self.config.set_project(project) {
             tracing::warn!("Failed to save project to config: {}", e);
         }
     }
 
-    pub async fn select_project(&mut self) {
-        if let Some(project) = self.available_projects.get(self.projects_selected) {
-            let project = project.clone();
-            self.switch_project(&project).await;
-            self.refresh().await;
+    /// Switch to a named context: its credentials, project, location and readonly flag
+    pub fn switch_context(&mut self, name: &str) {
+        if let Err(e) = self.config.use_context(name) {
+            self.show_error(&e.to_string());
+            return;
+        }
+        let Some(context) = self.config.context().cloned() else {
+            return;
+        };
+        TokenProvider::configure(self.config.credential_config());
+        self.readonly = self.cli_readonly || context.readonly;
+
+        if let Some(project) = &context.project {
+            self.switch_project(project);
+        }
+        if let Some(zone) = &context.zone {
+            self.switch_zone(zone);
+        }
+        let region = context
+            .region
+            .clone()
+            .unwrap_or_else(|| derive_region(&self.zone));
+        self.switch_region(&region);
+
+        // Other credentials see other projects and folders
+        self.project_picker = ProjectPicker::default();
+        self.reload_projects();
+        self.notify(&format!("Switched to context {}", name), false);
+        self.refresh();
+    }
+
+    /// Show the defined contexts and which one is active
+    fn show_contexts(&mut self) {
+        let names = self.config.context_names();
+        if names.is_empty() {
+            self.show_warning(
+                "No contexts defined, add them under 'contexts' in the config file or create gcloud configurations",
+            );
+            return;
+        }
+        let active = self.config.active_context.as_deref();
+        let names: Vec<String> = names
+            .into_iter()
+            .map(|name| {
+                if Some(name) == active {
+                    format!("{} (active)", name)
+                } else {
+                    name.to_string()
+                }
+            })
+            .collect();
+        self.notify(&format!("Contexts: {}", names.join(", ")), false);
+    }
+
+    /// Show who the credentials act as, fetching a token first if needed
+    fn whoami(&mut self) {
+        let tx = self.task_tx.clone();
+        tokio::spawn(async move {
+            let result = match TokenProvider::get_token().await {
+                Ok(_) => TokenProvider::identity()
+                    .ok_or_else(|| "No token cached for the current credentials".to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            let _ = tx.send(TaskMessage::Whoami { result });
+        });
+    }
+
+    /// Fetch the project list again in a background task
+    fn reload_projects(&mut self) {
+        let client = self.client.clone();
+        let tx = self.task_tx.clone();
+        tokio::spawn(async move {
+            let result = client.list_projects().await.map_err(|e| e.to_string());
+            let _ = tx.send(TaskMessage::ProjectsListed { result });
+        });
+    }
+
+    /// Switch to the selected project, or fold the selected folder
+    pub fn select_project(&mut self) {
+        let rows = self.project_rows();
+        match rows.get(self.project_picker.selected) {
+            Some(PickerRow::Project { index, .. }) => {
+                let project = self.available_projects[*index].project_id.clone();
+                self.switch_project(&project);
+                self.refresh();
+            }
+            Some(PickerRow::Container { index, .. }) => {
+                let name = self.project_picker.containers[*index].name.clone();
+                if !self.project_picker.collapsed.remove(&name) {
+                    self.project_picker.collapsed.insert(name);
+                }
+                return;
+            }
+            None => {}
         }
         self.exit_mode();
     }
 
-    pub async fn select_zone(&mut self) {
-        if let Some(zone) = self.available_zones.get(self.zones_selected) {
-            let zone = zone.clone();
-            self.switch_zone(&zone).await;
-            self.refresh().await;
+    pub fn select_zone(&mut self) {
+        let selected = self
+            .filtered_locations()
+            .get(self.zones_selected)
+            .map(|l| l.name.clone());
+        if let Some(name) = selected {
+            match self.location_kind {
+                LocationKind::Zone => self.switch_zone(&name),
+                LocationKind::Region => self.switch_region(&name),
+            }
+            self.refresh();
         }
         self.exit_mode();
     }
 
+    /// Switch to the favourite zone of a number key
+    pub fn switch_to_favorite_zone(&mut self, index: usize) {
+        match self.config.effective_favorite_zones().get(index) {
+            Some(zone) => {
+                let zone = zone.clone();
+                self.switch_zone(&zone);
+                self.refresh();
+            }
+            None => self.notify(&format!("No favourite zone on key {}", index), false),
+        }
+    }
+
     // =========================================================================
     // Command Execution
     // =========================================================================
 
-    pub async fn execute_command(&mut self) -> bool {
+    pub fn execute_command(&mut self) -> bool {
         // Use preview if user navigated to a suggestion, otherwise use typed text
         let command_text = if self.command_text.is_empty() {
             self.command_preview.clone().unwrap_or_default()
@@ -822,7 +2166,7 @@ impl App {
         match cmd {
             "q" | "quit" => return true,
             "back" => {
-                self.navigate_back().await;
+                self.navigate_back();
             }
             "projects" => {
                 self.enter_projects_mode();
@@ -832,13 +2176,51 @@ impl App {
                 self.enter_zones_mode();
                 return false; // Don't reset mode
             }
+            "regions" => {
+                self.enter_regions_mode();
+                return false; // Don't reset mode
+            }
+            "operations" | "ops" => {
+                self.enter_operations_mode();
+                return false; // Don't reset mode
+            }
+            "filter" => {
+                // Keep the expression as typed, it may contain spaces
+                let expr = command_text.trim_start()["filter".len()..].trim();
+                self.set_server_filter(expr);
+                if self.mode == Mode::Warning {
+                    return false; // Keep the error dialog open
+                }
+            }
+            "export" => {
+                // The rest of the line, so paths may contain spaces
+                let args = command_text.trim_start()[cmd.len()..].to_string();
+                self.export(&args);
+                if self.mode == Mode::Warning {
+                    return false; // Keep the error dialog open
+                }
+            }
             "zone" if parts.len() > 1 => {
-                self.switch_zone(parts[1]).await;
-                self.refresh().await;
+                self.switch_zone(parts[1]);
+                self.refresh();
             }
+            "region" if parts.len() > 1 => {
+                self.switch_region(parts[1]);
+                self.refresh();
+            }
+            "ctx" | "context" => {
+                match parts.get(1) {
+                    Some(name) => self.switch_context(name),
+                    None => self.show_contexts(),
+                }
+                if self.mode == Mode::Warning {
+                    return false; // Keep the error dialog open
+                }
+            }
+            "whoami" => self.whoami(),
             "project" if parts.len() > 1 => {
-                self.switch_project(parts[1]).await;
-                self.refresh().await;
+                self.switch_project(parts[1]);
+                self.refresh();
             }
             _ => {
                 // Check if it's a known resource
@@ -847,12 +2229,12 @@ impl App {
                     if let Some(resource) = self.current_resource() {
                         let is_sub = resource.sub_resources.iter().any(|s| s.resource_key == cmd);
                         if is_sub && self.selected_item().is_some() {
-                            self.navigate_to_sub_resource(cmd).await;
+                            self.navigate_to_sub_resource(cmd);
                         } else {
-                            self.navigate_to_resource(cmd).await;
+                            self.navigate_to_resource(cmd);
                         }
                     } else {
-                        self.navigate_to_resource(cmd).await;
+                        self.navigate_to_resource(cmd);
                     }
                 } else {
                     self.error = Some(format!("Unknown command: {}", cmd));
@@ -906,7 +2288,7 @@ impl App {
             .collect()
     }
 
-    /// Trigger an action by index - either execute immediately or show confirmation
+    /// Trigger an action by index - prompt for parameters, show confirmation or queue for execution
     pub fn trigger_action(&mut self, action_index: usize) {
         // Block actions in readonly mode
         if self.readonly {
@@ -922,44 +2304,119 @@ impl App {
             return;
         };
 
-        let Some(item) = self.selected_item() else {
+        let targets = self.action_targets();
+        let Some(item) = targets.first() else {
             self.show_warning("No item selected");
             return;
         };
 
-        // Get item name for display
-        let item_name = extract_json_value(item, &resource.name_field);
-        let item_id = extract_json_value(item, &resource.id_field);
-
-        // Check if action requires confirmation
-        if let Some(confirm) = &action.confirm {
-            let message = confirm
-                .message
-                .replace("{name}", &item_name)
-                .replace("{id}", &item_id);
+        // Actions with parameters collect their input first
+        if !action.params.is_empty() {
+            // Bulk forms start from the first target's values
+            let title = if targets.len() > 1 {
+                format!(
+                    "{} {} {}",
+                    action.display_name,
+                    targets.len(),
+                    resource.display_name
+                )
+            } else {
+                let item_name = extract_json_value(item, &resource.name_field);
+                format!("{} '{}'", action.display_name, item_name)
+            };
+            let fields = action
+                .params
+                .iter()
+                .map(|param| FormField {
+                    value: param.initial_value(item),
+                    param: param.clone(),
+                })
+                .collect();
 
-            self.pending_action = Some(PendingAction {
-                message,
-                destructive: confirm.destructive,
-                selected_yes: false, // Default to No for safety
-                action_key: action_index.to_string(),
-                resource_id: item_id,
+            self.action_form = Some(ActionForm {
+                action_index,
+                title,
+                fields,
+                focused: 0,
+                error: None,
             });
+            self.mode = Mode::Input;
+            return;
+        }
+
+        self.queue_action(action_index, HashMap::new());
+    }
+
+    /// Validate the input form and queue its action
+    /// Leaves the form open with an error message if validation fails
+    pub fn submit_action_form(&mut self) {
+        let Some(form) = self.action_form.as_mut() else {
+            return;
+        };
+
+        match form.validate() {
+            Ok(params) => {
+                let action_index = form.action_index;
+                self.action_form = None;
+                self.mode = Mode::Normal;
+                self.queue_action(action_index, params);
+            }
+            Err(e) => form.error = Some(e),
+        }
+    }
+
+    /// Set the pending action, asking for confirmation if the action requires it
+    /// Bulk actions are always confirmed with the list of targets
+    fn queue_action(&mut self, action_index: usize, params: HashMap<String, Value>) {
+        let Some(resource) = self.current_resource() else {
+            return;
+        };
+
+        let Some(action) = resource.actions.get(action_index) else {
+            return;
+        };
+
+        let targets = self.action_targets();
+        let destructive = action.confirm.as_ref().is_some_and(|c| c.destructive);
+
+        let message = match targets.as_slice() {
+            [] => {
+                self.show_warning("No item selected");
+                return;
+            }
+            [item] => confirm_message(resource, action, item, &params),
+            items => {
+                let names: Vec<String> = items
+                    .iter()
+                    .map(|i| extract_json_value(i, &resource.name_field))
+                    .collect();
+                Some(format!(
+                    "{} {} {}?\n\n{}",
+                    action.display_name,
+                    items.len(),
+                    resource.display_name,
+                    list_names(&names)
+                ))
+            }
+        };
+
+        self.pending_action = Some(PendingAction {
+            // Default to No for safety
+            selected_yes: message.is_none(),
+            message: message.clone().unwrap_or_default(),
+            destructive,
+            action_key: action_index.to_string(),
+            targets,
+            params,
+        });
+        if message.is_some() {
+            self.dialog_scroll = 0;
             self.mode = Mode::Confirm;
-        } else {
-            // Execute immediately
-            self.pending_action = Some(PendingAction {
-                message: String::new(),
-                destructive: false,
-                selected_yes: true,
-                action_key: action_index.to_string(),
-                resource_id: item_id,
-            });
         }
     }
 
-    /// Execute the pending action
-    pub async fn execute_pending_action(&mut self) {
+    /// Execute the pending action in a background task
+    pub fn execute_pending_action(&mut self) {
         let Some(pending) = self.pending_action.take() else {
             return;
         };
@@ -982,30 +2439,855 @@ impl App {
             return;
         };
 
-        let Some(item) = self.selected_item().cloned() else {
-            self.show_warning("No item selected");
-            return;
-        };
-
         let action_name = resource
             .actions
             .get(action_index)
             .map(|a| a.display_name.clone())
             .unwrap_or_else(|| "Unknown".to_string());
 
-        self.loading = true;
         self.mode = Mode::Normal;
 
-        match execute_action(&self.client, resource, action_index, &item).await {
-            Ok(_) => {
-                // Action succeeded - refresh to see updated state
-                self.refresh().await;
+        let client = self.client.clone();
+        let resource_key = self.resource_key.clone();
+        let tx = self.task_tx.clone();
+        let params = pending.params;
+
+        let mut targets = pending.targets;
+        if targets.len() > 1 {
+            let description = format!(
+                "{} {} {}",
+                action_name,
+                targets.len(),
+                resource.display_name
+            );
+            self.notify(&format!("{}...", description), false);
+            self.marked.clear();
+
+            let concurrency = self.config.effective_bulk_concurrency();
+            tokio::spawn(async move {
+                let results =
+                    execute_bulk(client, resource, action_index, targets, params, concurrency)
+                        .await;
+                let _ = tx.send(TaskMessage::BulkFinished {
+                    description,
+                    action_name,
+                    resource_key,
+                    api_base: resource.api.base.clone(),
+                    results,
+                });
+            });
+            return;
+        }
+
+        let Some(item) = targets.pop() else {
+            self.show_warning("No item selected");
+            return;
+        };
+
+        let item_name = extract_json_value(&item, &resource.name_field);
+        let description = format!("{} '{}'", action_name, item_name);
+        self.notify(&format!("{}...", description), false);
+
+        tokio::spawn(async move {
+            let result = execute_action(&client, resource, action_index, &item, &params)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send(TaskMessage::ActionFinished {
+                description,
+                resource_key,
+                api_base: resource.api.base.clone(),
+                result,
+            });
+        });
+    }
+
+    /// Track operations of a finished bulk action and summarize the results
+    fn apply_bulk_results(
+        &mut self,
+        description: String,
+        action_name: String,
+        resource_key: String,
+        api_base: String,
+        results: Vec<(String, Result<Value, String>)>,
+    ) {
+        let mut failed = Vec::new();
+        let mut succeeded = Vec::new();
+        for (name, result) in results {
+            match result {
+                Ok(response) => {
+                    if let Some(op) = detect_operation(&api_base, &response) {
+                        let status = parse_status(op.kind, &response);
+                        self.track_operation(
+                            format!("{} '{}'", action_name, name),
+                            resource_key.clone(),
+                            op,
+                            status,
+                        );
+                    }
+                    succeeded.push(name);
+                }
+                Err(e) => failed.push(format!("{}: {}", name, e)),
             }
-            Err(e) => {
-                self.error = Some(format!("Action '{}' failed: {}", action_name, e));
+        }
+
+        if resource_key == self.resource_key {
+            self.refresh_requested = true;
+        }
+
+        let headline = format!(
+            "{}: {} succeeded, {} failed",
+            description,
+            succeeded.len(),
+            failed.len()
+        );
+        if self.mode != Mode::Normal {
+            self.notify(&headline, !failed.is_empty());
+            return;
+        }
+
+        // Failures first, they are what needs attention
+        let mut summary = headline;
+        if !failed.is_empty() {
+            summary.push_str(&format!("\n\nFailed:\n{}", list_names(&failed)));
+        }
+        if !succeeded.is_empty() {
+            summary.push_str(&format!("\n\nSucceeded:\n{}", list_names(&succeeded)));
+        }
+        if failed.is_empty() {
+            self.show_warning(&summary);
+        } else {
+            self.show_error(&summary);
+        }
+    }
+
+    // =========================================================================
+    // Operations
+    // =========================================================================
+
+    /// Start tracking an operation returned by an action
+    fn track_operation(
+        &mut self,
+        description: String,
+        resource_key: String,
+        op: OperationRef,
+        status: OperationStatus,
+    ) {
+        let id = self.next_operation_id;
+        self.next_operation_id += 1;
+
+        tracing::info!("Tracking operation {} for {}", op.name, description);
+
+        let finished = status.is_finished();
+        if !finished {
+            spawn_poller(
+                self.client.clone(),
+                id,
+                op.clone(),
+                self.operation_tx.clone(),
+            );
+        }
+
+        self.operations.insert(
+            0,
+            TrackedOperation {
+                id,
+                description,
+                resource_key,
+                op,
+                status: OperationStatus::running(),
+                started: Instant::now(),
+                finished: None,
+            },
+        );
+
+        if finished {
+            self.apply_operation_update(OperationUpdate { id, status });
+        } else {
+            self.notify(
+                &format!("{} started", self.operations[0].description),
+                false,
+            );
+        }
+    }
+
+    /// Apply status updates sent by operation pollers (non-blocking)
+    pub fn process_operation_updates(&mut self) {
+        while let Ok(update) = self.operation_rx.try_recv() {
+            self.apply_operation_update(update);
+        }
+    }
+
+    fn apply_operation_update(&mut self, update: OperationUpdate) {
+        let Some(op) = self.operations.iter_mut().find(|o| o.id == update.id) else {
+            return;
+        };
+
+        op.status = update.status;
+        if !op.status.is_finished() {
+            return;
+        }
+        op.finished = Some(Instant::now());
+
+        let description = op.description.clone();
+        let on_current_resource = op.resource_key == self.resource_key;
+
+        match op.status.state {
+            OperationState::Done => {
+                self.notify(&format!("{} completed", description), false);
+            }
+            OperationState::Failed => {
+                let payload = op
+                    .status
+                    .error
+                    .as_ref()
+                    .map(|e| e.to_string())
+                    .unwrap_or_else(|| "unknown error".to_string());
+                // The panel keeps the error for later inspection
+                self.report_error(&format!("{} failed: {}", description, payload));
             }
+            OperationState::Running => {}
+        }
+
+        if on_current_resource {
+            self.refresh_requested = true;
+        }
+    }
+
+    /// Number of operations still running
+    pub fn running_operations(&self) -> usize {
+        self.operations
+            .iter()
+            .filter(|o| !o.status.is_finished())
+            .count()
+    }
+
+    /// Remove finished operations from the panel
+    pub fn clear_finished_operations(&mut self) {
+        self.operations.retain(|o| !o.status.is_finished());
+        self.operations_selected = 0;
+    }
+
+    /// Show the error payload of the selected operation
+    pub fn show_selected_operation_error(&mut self) {
+        let Some(op) = self.operations.get(self.operations_selected) else {
+            return;
+        };
+        if let Some(error) = &op.status.error {
+            let message = format!(
+                "{} failed: {}",
+                op.description,
+                serde_json::to_string_pretty(error).unwrap_or_default()
+            );
+            self.show_error(&message);
         }
+    }
 
-        self.loading = false;
+    // =========================================================================
+    // Notifications
+    // =========================================================================
+
+    /// Report an error from a background task
+    /// Shows the error modal in Normal mode; dialogs and input are not
+    /// interrupted, the error goes to the crumb instead
+    pub fn report_error(&mut self, message: &str) {
+        if self.mode == Mode::Normal {
+            self.show_error(message);
+        } else {
+            self.notify(message, true);
+        }
+    }
+
+    /// Show a short-lived message in the crumb
+    pub fn notify(&mut self, message: &str, is_error: bool) {
+        self.notification = Some(Notification {
+            message: message.to_string(),
+            is_error,
+            created: Instant::now(),
+        });
+    }
+
+    /// Current notification if it hasn't expired yet
+    pub fn active_notification(&self) -> Option<&Notification> {
+        self.notification
+            .as_ref()
+            .filter(|n| n.created.elapsed() < NOTIFICATION_TTL)
+    }
+}
+
+/// Run an action on every target with at most `concurrency` requests in flight
+/// Results are returned in target order, paired with the item name
+async fn execute_bulk(
+    client: GcpClient,
+    resource: &'static ResourceDef,
+    action_index: usize,
+    targets: Vec<Value>,
+    params: HashMap<String, Value>,
+    concurrency: usize,
+) -> Vec<(String, Result<Value, String>)> {
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let params = Arc::new(params);
+    let mut tasks = JoinSet::new();
+
+    for (index, item) in targets.into_iter().enumerate() {
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        let params = params.clone();
+        tasks.spawn(async move {
+            let name = extract_json_value(&item, &resource.name_field);
+            let result = match semaphore.acquire().await {
+                Ok(_permit) => execute_action(&client, resource, action_index, &item, &params)
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            (index, name, result)
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(result) => results.push(result),
+            Err(e) => tracing::error!("Bulk action task failed: {}", e),
+        }
+    }
+    results.sort_by_key(|(index, _, _)| *index);
+    results
+        .into_iter()
+        .map(|(_, name, result)| (name, result))
+        .collect()
+}
+
+/// One name per line, every one of them; the dialogs scroll
+fn list_names(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|n| format!("  {}", n))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Sort declared by a resource definition
+fn default_sort(resource_key: &str) -> Option<SortState> {
+    let resource = get_resource(resource_key)?;
+    let sort = resource.default_sort.as_ref()?;
+    Some(SortState {
+        column: resource.column_index(&sort.column)?,
+        descending: sort.descending,
+    })
+}
+
+/// Whether a browser can be launched: not over SSH, and on Linux only with a display
+fn browser_available() -> bool {
+    if std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some() {
+        return false;
+    }
+    if cfg!(target_os = "linux") {
+        return std::env::var_os("DISPLAY").is_some()
+            || std::env::var_os("WAYLAND_DISPLAY").is_some();
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gcp::auth::AuthSource;
+    use serde_json::json;
+
+    async fn test_app() -> App {
+        let client = GcpClient::new(None, Some("test-project".to_string()))
+            .await
+            .unwrap();
+        App::from_initialized(
+            client,
+            "test-project".to_string(),
+            "us-central1-a".to_string(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Config::default(),
+            false,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_stale_list_response_is_discarded() {
+        let mut app = test_app().await;
+        app.list_request_id = 2;
+        app.loading = true;
+
+        // Response to a request superseded by navigation
+        app.apply_task_message(TaskMessage::Listed {
+            request_id: 1,
+            result: Ok(vec![json!({"name": "old"})]),
+        });
+        assert!(app.items.is_empty());
+        assert!(app.loading);
+
+        app.apply_task_message(TaskMessage::ListProgress {
+            request_id: 1,
+            progress: ListProgress::default(),
+        });
+        assert!(app.list_progress.is_none());
+
+        app.apply_task_message(TaskMessage::Listed {
+            request_id: 2,
+            result: Ok(vec![json!({"name": "new"})]),
+        });
+        assert_eq!(app.items.len(), 1);
+        assert_eq!(app.filtered_items.len(), 1);
+        assert!(!app.loading);
+    }
+
+    #[tokio::test]
+    async fn test_sort_survives_refresh() {
+        let mut app = test_app().await;
+        app.resource_key = "snapshots".to_string();
+        app.sort = default_sort("snapshots");
+        assert!(app.sort.is_some_and(|s| s.descending));
+
+        let snapshot =
+            |name: &str, created: &str| json!({"name": name, "creationTimestamp": created});
+        let names = |app: &App| -> Vec<String> {
+            app.filtered_items
+                .iter()
+                .map(|i| i["name"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        app.list_request_id = 1;
+        app.apply_task_message(TaskMessage::Listed {
+            request_id: 1,
+            result: Ok(vec![
+                snapshot("old", "2023-05-01T00:00:00Z"),
+                snapshot("new", "2024-05-01T00:00:00Z"),
+            ]),
+        });
+        assert_eq!(names(&app), vec!["new", "old"]);
+
+        // Reversing keeps the selected item selected
+        app.selected = 1;
+        app.toggle_sort_order();
+        assert_eq!(names(&app), vec!["old", "new"]);
+        assert_eq!(app.selected, 0);
+
+        app.list_request_id = 2;
+        app.apply_task_message(TaskMessage::Listed {
+            request_id: 2,
+            result: Ok(vec![
+                snapshot("newest", "2025-05-01T00:00:00Z"),
+                snapshot("new", "2024-05-01T00:00:00Z"),
+                snapshot("old", "2023-05-01T00:00:00Z"),
+            ]),
+        });
+        assert_eq!(names(&app), vec!["old", "new", "newest"]);
+    }
+
+    #[tokio::test]
+    async fn test_yank_values() {
+        let mut app = test_app().await;
+        let vm = json!({
+            "name": "vm-1",
+            "id": "42",
+            "status": "RUNNING",
+            "selfLink": "https://compute.googleapis.com/compute/v1/projects/p/zones/z/instances/vm-1"
+        });
+        let vms = get_resource("vm-instances").unwrap();
+        let value =
+            |app: &App, resource, item: &Value, target| app.yank_value(resource, item, target);
+
+        assert_eq!(
+            value(&app, vms, &vm, YankTarget::Name).as_deref(),
+            Some("vm-1")
+        );
+        assert_eq!(value(&app, vms, &vm, YankTarget::Id).as_deref(), Some("42"));
+        assert_eq!(
+            value(&app, vms, &vm, YankTarget::Column(1)).as_deref(),
+            Some("RUNNING")
+        );
+        assert_eq!(
+            value(&app, vms, &vm, YankTarget::Link).as_deref(),
+            vm["selfLink"].as_str()
+        );
+        // Missing cells and columns have nothing to copy
+        assert_eq!(value(&app, vms, &vm, YankTarget::Column(2)), None);
+        assert_eq!(value(&app, vms, &vm, YankTarget::Column(99)), None);
+
+        // Without a selfLink the link is built from the get request
+        let services = get_resource("cloudrun-services").unwrap();
+        let service = json!({"name": "projects/p/locations/us-central1/services/web"});
+        assert_eq!(
+            value(&app, services, &service, YankTarget::Link).as_deref(),
+            Some("https://run.googleapis.com/v2/projects/p/locations/us-central1/services/web")
+        );
+
+        // Marked items are copied one per line
+        app.resource_key = "vm-instances".to_string();
+        app.items = vec![vm.clone(), json!({"name": "vm-2", "id": "43"})];
+        app.apply_filter();
+        app.toggle_mark_all();
+        app.yank_pending = true;
+        app.yank(YankTarget::Name);
+        assert!(!app.yank_pending);
+        let notification = app.active_notification().unwrap();
+        assert_eq!(notification.message, "Copied name of 2 items");
+    }
+
+    #[tokio::test]
+    async fn test_all_regions_adds_location_column() {
+        let mut app = test_app().await;
+        app.resource_key = "scheduler-jobs".to_string();
+        let columns = app.current_resource().unwrap().columns.len();
+
+        app.region = parse_region("all");
+        app.client.region = app.region.clone();
+        assert_eq!(app.current_resource().unwrap().columns.len(), columns + 1);
+
+        // Zonal and global resources are unaffected
+        app.resource_key = "buckets".to_string();
+        assert_eq!(
+            app.current_resource().unwrap().columns.len(),
+            get_resource("buckets").unwrap().columns.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_all_zones_label() {
+        let mut app = test_app().await;
+        app.set_all_zones(true);
+        assert_eq!(app.zone_label(), "all");
+        // The zone is kept for resources without an all-zones view
+        assert_eq!(app.client.zone, "us-central1-a");
+
+        app.set_all_zones(false);
+        assert_eq!(app.zone_label(), "us-central1-a");
+    }
+
+    #[tokio::test]
+    async fn test_zone_picker_search() {
+        let mut app = test_app().await;
+        let zones = [
+            "asia-east1-a",
+            "europe-west1-b",
+            "us-central1-a",
+            "us-east1-b",
+        ]
+        .iter()
+        .map(|z| ComputeLocation {
+            name: z.to_string(),
+            status: "UP".to_string(),
+        })
+        .collect::<Vec<_>>();
+
+        // Results for a previous project are dropped
+        app.apply_task_message(TaskMessage::LocationsListed {
+            project: "other-project".to_string(),
+            kind: LocationKind::Zone,
+            result: Ok(zones.clone()),
+        });
+        assert!(app.available_zones.is_empty());
+
+        app.apply_task_message(TaskMessage::LocationsListed {
+            project: "test-project".to_string(),
+            kind: LocationKind::Zone,
+            result: Ok(zones),
+        });
+        app.enter_zones_mode();
+        assert_eq!(app.mode, Mode::Zones);
+        assert_eq!(app.zones_selected, 2); // The current zone
+
+        for c in "east".chars() {
+            app.push_location_query(c);
+        }
+        let names: Vec<&str> = app
+            .filtered_locations()
+            .iter()
+            .map(|l| l.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["us-east1-b", "asia-east1-a"]);
+        assert_eq!(app.zones_selected, 0);
+
+        app.next();
+        app.next();
+        assert_eq!(app.zones_selected, 1);
+    }
+
+    #[tokio::test]
+    async fn test_zone_picker_fallback() {
+        let mut app = test_app().await;
+        app.location_kind = LocationKind::Region;
+        app.mode = Mode::Zones;
+
+        // e.g. the Compute Engine API is disabled in the project
+        app.apply_task_message(TaskMessage::LocationsListed {
+            project: "test-project".to_string(),
+            kind: LocationKind::Region,
+            result: Err("API not enabled".to_string()),
+        });
+        let names: Vec<&str> = app
+            .available_regions
+            .iter()
+            .map(|l| l.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "asia-east1",
+                "asia-northeast1",
+                "europe-west1",
+                "us-central1",
+                "us-east1",
+                "us-west1"
+            ]
+        );
+        assert_eq!(app.zones_selected, 3); // The current region
+        assert!(app.notification.as_ref().is_some_and(|n| n.is_error));
+    }
+
+    #[tokio::test]
+    async fn test_export_writes_filtered_items() {
+        let mut app = test_app().await;
+        app.items = vec![
+            json!({"name": "vm-1", "status": "RUNNING"}),
+            json!({"name": "vm-2", "status": "TERMINATED"}),
+        ];
+        app.filter_text = "status=RUNNING".to_string();
+        app.apply_filter();
+
+        let path = std::env::temp_dir().join(format!("tgcp export {}.md", std::process::id()));
+        app.export(path.to_str().unwrap());
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(text.contains("- **project**: test-project\n"));
+        assert!(text.contains("- **filter**: status=RUNNING\n"));
+        assert!(text.contains("| vm-1 | RUNNING |"));
+        assert!(!text.contains("vm-2"));
+        assert!(app.active_notification().is_some_and(|n| !n.is_error));
+
+        // Errors are shown instead of writing anything
+        app.export("no-extension");
+        assert_eq!(app.mode, Mode::Warning);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_action_confirms_every_target() {
+        let mut app = test_app().await;
+        app.items = vec![
+            json!({"name": "vm-1", "id": "1"}),
+            json!({"name": "vm-2", "id": "2"}),
+            json!({"name": "db-1", "id": "3"}),
+        ];
+        app.filter_text = "vm".to_string();
+        app.apply_filter();
+
+        app.toggle_mark_all();
+        assert_eq!(app.marked.len(), 2);
+
+        // Marks survive the filter being cleared
+        app.clear_filter();
+        app.selected = 2;
+        let stop = app.find_action_by_shortcut("x").unwrap();
+        app.trigger_action(stop);
+
+        assert_eq!(app.mode, Mode::Confirm);
+        let pending = app.pending_action.as_ref().unwrap();
+        assert_eq!(pending.targets.len(), 2);
+        assert!(pending.destructive);
+        assert!(pending.message.contains("vm-1") && pending.message.contains("vm-2"));
+        assert!(!pending.message.contains("db-1"));
+
+        // Marked rows hidden by a later filter are left out
+        app.exit_mode();
+        app.filter_text = "vm-2".to_string();
+        app.apply_filter();
+        assert_eq!(
+            app.action_targets(),
+            vec![json!({"name": "vm-2", "id": "2"})]
+        );
+        assert_eq!(app.marked_shown(), 1);
+        app.clear_filter();
+
+        // Unmarking one of two leaves a single target with the action's own message
+        app.exit_mode();
+        app.selected = 0;
+        app.toggle_mark();
+        app.trigger_action(stop);
+        let pending = app.pending_action.as_ref().unwrap();
+        assert_eq!(pending.targets, vec![json!({"name": "vm-2", "id": "2"})]);
+        assert_eq!(pending.message, "Stop instance 'vm-2'?");
+    }
+
+    #[tokio::test]
+    async fn test_bulk_results_are_summarized() {
+        let mut app = test_app().await;
+        app.apply_task_message(TaskMessage::BulkFinished {
+            description: "Stop 2 VM Instances".to_string(),
+            action_name: "Stop".to_string(),
+            resource_key: "vm-instances".to_string(),
+            api_base: "https://compute.googleapis.com/compute/v1".to_string(),
+            results: vec![
+                ("vm-1".to_string(), Ok(json!({}))),
+                ("vm-2".to_string(), Err("permission denied".to_string())),
+            ],
+        });
+
+        assert_eq!(app.mode, Mode::Warning);
+        let summary = app.error.as_ref().unwrap();
+        assert!(summary.starts_with("Stop 2 VM Instances: 1 succeeded, 1 failed"));
+        let failed = summary.find("vm-2: permission denied").unwrap();
+        assert!(failed < summary.find("vm-1").unwrap());
+        assert!(app.refresh_requested);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_dialogs_list_every_target() {
+        let mut app = test_app().await;
+        app.items = (0..40)
+            .map(|i| json!({"name": format!("vm-{}", i), "id": i.to_string()}))
+            .collect();
+        app.apply_filter();
+        app.toggle_mark_all();
+        let stop = app.find_action_by_shortcut("x").unwrap();
+        app.trigger_action(stop);
+
+        let pending = app.pending_action.as_ref().unwrap();
+        let listed: Vec<&str> = pending.message.lines().skip(2).collect();
+        assert_eq!(listed.len(), 40);
+        assert_eq!(listed[39], "  vm-39");
+
+        // Scrolling stops at the last page the renderer reported
+        app.dialog_max_scroll.set(2);
+        for _ in 0..5 {
+            app.scroll_dialog(true);
+        }
+        assert_eq!(app.dialog_scroll, 2);
+        app.scroll_dialog(false);
+        assert_eq!(app.dialog_scroll, 1);
+
+        // Every failure keeps its name and error
+        app.exit_mode();
+        app.apply_task_message(TaskMessage::BulkFinished {
+            description: "Stop 40 VM Instances".to_string(),
+            action_name: "Stop".to_string(),
+            resource_key: "vm-instances".to_string(),
+            api_base: "https://compute.googleapis.com/compute/v1".to_string(),
+            results: (0..40)
+                .map(|i| (format!("vm-{}", i), Err(format!("error {}", i))))
+                .collect(),
+        });
+        assert_eq!(app.dialog_scroll, 0);
+        let summary = app.error.as_ref().unwrap();
+        assert!((0..40).all(|i| summary.contains(&format!("vm-{}: error {}", i, i))));
+    }
+
+    #[tokio::test]
+    async fn test_action_failure_does_not_interrupt_dialogs() {
+        let mut app = test_app().await;
+        app.mode = Mode::Help;
+
+        app.apply_task_message(TaskMessage::ActionFinished {
+            description: "Stop 'vm-1'".to_string(),
+            resource_key: "vm-instances".to_string(),
+            api_base: "https://compute.googleapis.com/compute/v1".to_string(),
+            result: Err("permission denied".to_string()),
+        });
+        assert_eq!(app.mode, Mode::Help);
+        assert!(app.active_notification().unwrap().is_error);
+
+        app.mode = Mode::Normal;
+        app.apply_task_message(TaskMessage::ActionFinished {
+            description: "Stop 'vm-1'".to_string(),
+            resource_key: "vm-instances".to_string(),
+            api_base: "https://compute.googleapis.com/compute/v1".to_string(),
+            result: Err("permission denied".to_string()),
+        });
+        assert_eq!(app.mode, Mode::Warning);
+    }
+
+    #[tokio::test]
+    async fn test_stale_describe_response_is_discarded() {
+        let mut app = test_app().await;
+        app.items = vec![json!({"name": "svc", "uid": "1"})];
+        app.apply_filter();
+        app.mode = Mode::Describe;
+        app.describe_data = app.selected_item().cloned();
+        app.describe_request_id = 2;
+        app.describe_loading = true;
+
+        app.apply_task_message(TaskMessage::Described {
+            request_id: 1,
+            result: Ok(json!({"name": "other"})),
+        });
+        assert_eq!(app.describe_data.as_ref().unwrap()["name"], "svc");
+        assert!(app.describe_loading);
+
+        app.apply_task_message(TaskMessage::Described {
+            request_id: 2,
+            result: Ok(json!({"name": "svc", "spec": {}})),
+        });
+        assert!(app.describe_data.as_ref().unwrap().get("spec").is_some());
+        assert!(!app.describe_loading);
+
+        // Closing the view makes in-flight responses stale
+        app.exit_mode();
+        app.apply_task_message(TaskMessage::Described {
+            request_id: 2,
+            result: Ok(json!({"name": "svc"})),
+        });
+        assert!(app.describe_data.is_none());
+    }
+
+    #[test]
+    fn test_whoami_message() {
+        let now = chrono::Utc::now();
+        let identity = TokenIdentity {
+            source: AuthSource::Impersonation(Box::new(AuthSource::AdcUser)),
+            principal: Some("viewer@acme.iam.gserviceaccount.com".to_string()),
+            scopes: vec!["https://www.googleapis.com/auth/cloud-platform".to_string()],
+            expires_at: now + chrono::Duration::minutes(42),
+        };
+        let message = whoami_message(&identity, now);
+        assert!(message.contains("Principal: viewer@acme.iam.gserviceaccount.com"));
+        assert!(message.contains("Source: impersonation via ADC user"));
+        assert!(message.contains("(in 42 min)"));
+        assert!(message.ends_with("https://www.googleapis.com/auth/cloud-platform"));
+
+        let expired = TokenIdentity {
+            principal: None,
+            scopes: Vec::new(),
+            expires_at: now - chrono::Duration::minutes(1),
+            ..identity
+        };
+        let message = whoami_message(&expired, now);
+        assert!(message.contains("Principal: unknown"));
+        assert!(message.contains("(expired)"));
+        assert!(message.contains("Scopes: unknown"));
+    }
+
+    #[tokio::test]
+    async fn test_whoami_dialog() {
+        let mut app = test_app().await;
+        app.apply_task_message(TaskMessage::Whoami {
+            result: Ok(TokenIdentity {
+                source: AuthSource::KeyFile,
+                principal: Some("ci@acme.iam.gserviceaccount.com".to_string()),
+                scopes: Vec::new(),
+                expires_at: chrono::Utc::now(),
+            }),
+        });
+        assert_eq!(app.mode, Mode::Warning);
+        assert!(app
+            .info_message
+            .as_deref()
+            .unwrap()
+            .contains("ci@acme.iam.gserviceaccount.com"));
+
+        app.apply_task_message(TaskMessage::Whoami {
+            result: Err("no credentials".to_string()),
+        });
+        assert!(app.info_message.is_none());
+        assert!(app.error.as_deref().unwrap().contains("no credentials"));
     }
 }
@@ -0,0 +1,478 @@
+//! Headless subcommands
+//!
+//! `tgcp list`, `tgcp describe` and `tgcp action` run a single request against
+//! the resource registry and print the result without starting the TUI.
+
+use anyhow::{anyhow, bail, Result};
+use clap::{Args, Subcommand, ValueEnum};
+use serde_json::Value;
+use std::collections::HashMap;
+use tokio::sync::mpsc::unbounded_channel;
+
+use crate::config::Config;
+use crate::export::{format_csv, format_markdown, format_table};
+use crate::filter::Filter;
+use crate::gcp::client::{parse_region, GcpClient, ALL_ZONES};
+use crate::gcp::dispatch::{confirm_message, describe_resource, execute_action, list_resources};
+use crate::gcp::operations::{
+    detect_operation, parse_status, spawn_poller, OperationState, OperationStatus,
+};
+use crate::resource::registry::{
+    extract_json_value, get_registry, get_resource, registry_report, ActionDef, ResourceDef,
+};
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// List resources of a type
+    List {
+        /// Resource key (e.g., vm-instances)
+        resource: String,
+        #[command(flatten)]
+        target: TargetArgs,
+        /// Only show matching items, same syntax as the / filter (e.g., status=RUNNING)
+        #[arg(long)]
+        filter: Option<String>,
+        /// Expression passed to the list API's own filter (e.g., "status = RUNNING")
+        #[arg(long)]
+        server_filter: Option<String>,
+        #[arg(short, long, value_enum, default_value = "table")]
+        output: OutputFormat,
+    },
+    /// Show a single resource
+    Describe {
+        /// Resource key (e.g., vm-instances)
+        resource: String,
+        /// Name or id of the resource
+        name: String,
+        #[command(flatten)]
+        target: TargetArgs,
+        #[arg(short, long, value_enum, default_value = "json")]
+        output: OutputFormat,
+    },
+    /// Run an action on a resource
+    Action {
+        /// Resource key (e.g., vm-instances)
+        resource: String,
+        /// Name or id of the resource
+        name: String,
+        /// Action name (e.g., stop, "Create Snapshot" or create-snapshot)
+        action: String,
+        #[command(flatten)]
+        target: TargetArgs,
+        /// Action parameter as key=value (repeatable), defaults apply otherwise
+        #[arg(long = "param", value_name = "KEY=VALUE")]
+        params: Vec<String>,
+        /// Skip the confirmation required by destructive actions
+        #[arg(short, long)]
+        yes: bool,
+        /// Wait for the returned operation to finish
+        #[arg(long)]
+        wait: bool,
+        #[arg(short, long, value_enum, default_value = "table")]
+        output: OutputFormat,
+    },
+}
+
+/// Project, zone and parent selection shared by all subcommands
+#[derive(Args, Debug)]
+pub struct TargetArgs {
+    /// GCP project (default: from config or credentials)
+    #[arg(long)]
+    project: Option<String>,
+
+    /// GCP zone, or "all" for zonal compute resources (default: from config)
+    #[arg(short, long)]
+    zone: Option<String>,
+
+    /// Region of regional resources, or "all" (default: from config)
+    #[arg(long)]
+    region: Option<String>,
+
+    /// Name of the parent item for sub-resources (e.g., the cluster of node-pools)
+    #[arg(long)]
+    parent: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Yaml,
+    Csv,
+    Md,
+}
+
+/// Run a headless subcommand and print its result to stdout
+pub async fn run(command: Command, config: Config, readonly: bool) -> Result<()> {
+    if let Some(report) = registry_report(usize::MAX) {
+        eprintln!("warning: {}", report);
+    }
+
+    match command {
+        Command::List {
+            resource,
+            target,
+            filter,
+            server_filter,
+            output,
+        } => {
+            let resource_def = lookup_resource(&resource)?;
+            let filter = Filter::parse(filter.as_deref().unwrap_or_default());
+            if let Some(e) = &filter.error {
+                bail!("Invalid filter: {}", e);
+            }
+            let server_filter = match (server_filter, &resource_def.api.filter) {
+                (None, _) => None,
+                (Some(expr), Some(def)) => Some(
+                    def.validate(&expr)
+                        .map_err(|e| anyhow!("Invalid server filter: {}", e))?,
+                ),
+                (Some(_), None) => bail!(
+                    "{} does not support server-side filtering, use --filter instead",
+                    resource
+                ),
+            };
+            let client = connect(&target, &config).await?;
+            let parent = resolve_parent(&client, &resource, target.parent.as_deref()).await?;
+
+            let mut last_progress = None;
+            let mut items = list_resources(
+                &client,
+                resource_def,
+                parent.as_ref(),
+                server_filter.as_deref(),
+                &mut |p| last_progress = Some(p),
+            )
+            .await?;
+            if let Some(p) = last_progress.filter(|p| p.truncated) {
+                eprintln!(
+                    "warning: stopped after {} pages, raise max_pages in config to see more",
+                    p.pages
+                );
+            }
+
+            items.retain(|item| filter.matches(Some(resource_def), item));
+
+            print!("{}", format_items(resource_def, &items, output)?);
+        }
+        Command::Describe {
+            resource,
+            name,
+            target,
+            output,
+        } => {
+            let resource_def = lookup_resource(&resource)?;
+            let client = connect(&target, &config).await?;
+            let parent = resolve_parent(&client, &resource, target.parent.as_deref()).await?;
+            let item = find_item(&client, resource_def, parent.as_ref(), &name).await?;
+            let item = describe_resource(&client, resource_def, &item, parent.as_ref())
+                .await?
+                .unwrap_or(item);
+
+            print!("{}", format_item(resource_def, &item, output)?);
+        }
+        Command::Action {
+            resource,
+            name,
+            action,
+            target,
+            params,
+            yes,
+            wait,
+            output,
+        } => {
+            if readonly {
+                bail!("Actions are not supported in read-only mode");
+            }
+
+            let resource_def = lookup_resource(&resource)?;
+            let action_index = find_action(resource_def, &action)?;
+            let action_def = &resource_def.actions[action_index];
+
+            let client = connect(&target, &config).await?;
+            let parent = resolve_parent(&client, &resource, target.parent.as_deref()).await?;
+            let item = find_item(&client, resource_def, parent.as_ref(), &name).await?;
+            let item_name = extract_json_value(&item, &resource_def.name_field);
+
+            let params = parse_params(action_def, &item, &params)?;
+
+            if let Some(message) = confirm_message(resource_def, action_def, &item, &params) {
+                if !yes {
+                    bail!("{} Pass --yes to confirm.", message);
+                }
+            }
+
+            let description = format!("{} '{}'", action_def.display_name, item_name);
+            let response =
+                execute_action(&client, resource_def, action_index, &item, &params).await?;
+
+            let mut status = None;
+            if let Some(op) = detect_operation(&resource_def.api.base, &response) {
+                let mut current = parse_status(op.kind, &response);
+                if wait && !current.is_finished() {
+                    eprintln!("Waiting for operation {}...", op.name);
+                    let (tx, mut rx) = unbounded_channel();
+                    spawn_poller(client.clone(), 0, op, tx);
+                    while let Some(update) = rx.recv().await {
+                        current = update.status;
+                        if current.is_finished() {
+                            break;
+                        }
+                    }
+                }
+                status = Some(current);
+            }
+
+            match output {
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Md => {
+                    println!("{}", action_summary(&description, status.as_ref()));
+                }
+                OutputFormat::Json | OutputFormat::Yaml => {
+                    print!("{}", format_value(&response, output)?);
+                }
+            }
+
+            if let Some(status) = status.filter(|s| s.state == OperationState::Failed) {
+                bail!(
+                    "{} failed: {}",
+                    description,
+                    status.error.unwrap_or(Value::Null)
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn lookup_resource(key: &str) -> Result<&'static ResourceDef> {
+    get_resource(key).ok_or_else(|| anyhow!("Unknown resource: {}", key))
+}
+
+/// Build a client for the target project, zone and region, falling back to config
+async fn connect(target: &TargetArgs, config: &Config) -> Result<GcpClient> {
+    let project = target
+        .project
+        .clone()
+        .or_else(|| config.effective_project());
+    let all_zones = target.zone.as_deref() == Some(ALL_ZONES);
+    let zone = target
+        .zone
+        .clone()
+        .filter(|zone| zone != ALL_ZONES)
+        .unwrap_or_else(|| config.effective_zone());
+    let region = match &target.region {
+        Some(region) => parse_region(region),
+        None => config.effective_region(&zone),
+    };
+
+    let mut client = GcpClient::new(Some(zone), project).await?;
+    client.max_pages = config.effective_max_pages();
+    client.region = region;
+    client.all_zones = all_zones;
+
+    if client.project.is_empty() {
+        bail!("No project set, pass --project or select one in the TUI first");
+    }
+    Ok(client)
+}
+
+/// Fetch the parent item of a sub-resource by name
+async fn resolve_parent(
+    client: &GcpClient,
+    resource_key: &str,
+    parent_name: Option<&str>,
+) -> Result<Option<Value>> {
+    let Some(parent_name) = parent_name else {
+        return Ok(None);
+    };
+
+    let parent_def = get_registry()
+        .resources
+        .values()
+        .find(|r| {
+            r.sub_resources
+                .iter()
+                .any(|s| s.resource_key == resource_key)
+        })
+        .ok_or_else(|| {
+            anyhow!(
+                "{} is not a sub-resource, --parent is not needed",
+                resource_key
+            )
+        })?;
+
+    find_item(client, parent_def, None, parent_name)
+        .await
+        .map(Some)
+}
+
+/// List a resource and pick the item whose name or id matches exactly
+async fn find_item(
+    client: &GcpClient,
+    resource: &ResourceDef,
+    parent: Option<&Value>,
+    name: &str,
+) -> Result<Value> {
+    let items = list_resources(client, resource, parent, None, &mut |_| {}).await?;
+    items
+        .into_iter()
+        .find(|item| {
+            extract_json_value(item, &resource.name_field) == name
+                || extract_json_value(item, &resource.id_field) == name
+        })
+        .ok_or_else(|| anyhow!("{} '{}' not found", resource.display_name, name))
+}
+
+/// Find an action by display name, case-insensitively with '-' or '_' for spaces
+fn find_action(resource: &ResourceDef, name: &str) -> Result<usize> {
+    let normalize = |s: &str| s.trim().to_lowercase().replace(['_', ' '], "-");
+    let wanted = normalize(name);
+
+    resource
+        .actions
+        .iter()
+        .position(|a| normalize(&a.display_name) == wanted)
+        .ok_or_else(|| {
+            let available: Vec<String> = resource
+                .actions
+                .iter()
+                .map(|a| normalize(&a.display_name))
+                .collect();
+            if available.is_empty() {
+                anyhow!("{} has no actions", resource.display_name)
+            } else {
+                anyhow!(
+                    "Unknown action '{}', available: {}",
+                    name,
+                    available.join(", ")
+                )
+            }
+        })
+}
+
+/// Validate `key=value` arguments against the action's parameters
+/// Parameters that are not given use their default for the item
+fn parse_params(
+    action: &ActionDef,
+    item: &Value,
+    args: &[String],
+) -> Result<HashMap<String, Value>> {
+    let mut given = HashMap::new();
+    for arg in args {
+        let (key, value) = arg
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Invalid --param '{}', expected KEY=VALUE", arg))?;
+        let key = key.trim();
+        if !action.params.iter().any(|p| p.name == key) {
+            bail!("'{}' has no parameter '{}'", action.display_name, key);
+        }
+        given.insert(key.to_string(), value.to_string());
+    }
+
+    let mut values = HashMap::new();
+    for param in &action.params {
+        let raw = given
+            .remove(&param.name)
+            .unwrap_or_else(|| param.initial_value(item));
+        let value = param.parse_value(&raw).map_err(|e| anyhow!(e))?;
+        values.insert(param.name.clone(), value);
+    }
+    Ok(values)
+}
+
+fn action_summary(description: &str, status: Option<&OperationStatus>) -> String {
+    match status.map(|s| &s.state) {
+        None | Some(OperationState::Done) => format!("{}: done", description),
+        Some(OperationState::Running) => format!("{}: started", description),
+        Some(OperationState::Failed) => format!("{}: failed", description),
+    }
+}
+
+/// Render a list of items; table, CSV and Markdown use the resource columns
+fn format_items(resource: &ResourceDef, items: &[Value], format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Table => Ok(format_table(&resource.columns, items)),
+        OutputFormat::Csv => Ok(format_csv(&resource.columns, items)),
+        OutputFormat::Md => Ok(format_markdown(&resource.columns, items)),
+        OutputFormat::Json | OutputFormat::Yaml => {
+            format_value(&Value::Array(items.to_vec()), format)
+        }
+    }
+}
+
+/// Render a single item; table shows one column per line
+fn format_item(resource: &ResourceDef, item: &Value, format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Table => {
+            let width = resource
+                .columns
+                .iter()
+                .map(|c| c.header.len())
+                .max()
+                .unwrap_or(0);
+            Ok(resource
+                .columns
+                .iter()
+                .map(|col| {
+                    format!(
+                        "{:width$}  {}\n",
+                        col.header.to_uppercase(),
+                        extract_json_value(item, &col.json_path),
+                        width = width
+                    )
+                })
+                .collect())
+        }
+        OutputFormat::Csv => Ok(format_csv(&resource.columns, std::slice::from_ref(item))),
+        OutputFormat::Md => Ok(format_markdown(
+            &resource.columns,
+            std::slice::from_ref(item),
+        )),
+        OutputFormat::Json | OutputFormat::Yaml => format_value(item, format),
+    }
+}
+
+fn format_value(value: &Value, format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Yaml => Ok(serde_yaml::to_string(value)?),
+        _ => Ok(format!("{}\n", serde_json::to_string_pretty(value)?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_find_action() {
+        let resource = get_resource("disks").unwrap();
+        let index = find_action(resource, "create-snapshot").unwrap();
+        assert_eq!(resource.actions[index].display_name, "Create Snapshot");
+        assert_eq!(find_action(resource, "Create Snapshot").unwrap(), index);
+        assert!(find_action(resource, "explode").is_err());
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let resource = get_resource("disks").unwrap();
+        let action = &resource.actions[find_action(resource, "resize").unwrap()];
+        let item = json!({"name": "disk-1", "sizeGb": "10"});
+
+        // Default resolved from the item
+        let params = parse_params(action, &item, &[]).unwrap();
+        assert_eq!(params["sizeGb"], json!(10));
+
+        let params = parse_params(action, &item, &["sizeGb=20".to_string()]).unwrap();
+        assert_eq!(params["sizeGb"], json!(20));
+
+        // Keys are trimmed before they are checked
+        let params = parse_params(action, &item, &[" sizeGb=30".to_string()]).unwrap();
+        assert_eq!(params["sizeGb"], json!(30));
+
+        assert!(parse_params(action, &item, &["sizeGb=big".to_string()]).is_err());
+        assert!(parse_params(action, &item, &["nope=1".to_string()]).is_err());
+        assert!(parse_params(action, &item, &["sizeGb".to_string()]).is_err());
+    }
+}
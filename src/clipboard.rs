@@ -0,0 +1,83 @@
+//! System clipboard access
+//!
+//! Copies are sent to the terminal with the OSC 52 escape sequence, which the
+//! terminal forwards to the local clipboard, so it also works over SSH. For
+//! terminals without OSC 52 support, `clipboard_command` in the config names a
+//! program (e.g., `pbcopy` or `xclip -selection clipboard`) that receives the
+//! text on stdin instead.
+
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// Copy text to the clipboard: piped to `command` if given, else with OSC 52
+pub fn copy(text: &str, command: Option<&str>) -> Result<()> {
+    match command.filter(|c| !c.trim().is_empty()) {
+        Some(command) => run_command(command, text)?,
+        None => {
+            let mut stdout = io::stdout();
+            stdout
+                .write_all(osc52_sequence(text, std::env::var_os("TMUX").is_some()).as_bytes())?;
+            stdout.flush()?;
+        }
+    }
+    tracing::debug!("Copied {} bytes to clipboard", text.len());
+    Ok(())
+}
+
+/// Run a clipboard command with the text on stdin
+fn run_command(command: &str, text: &str) -> Result<()> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().unwrap_or_default();
+
+    // Output would draw over the TUI
+    let mut child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("cannot run {}", program))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
+
+/// Build the OSC 52 sequence, wrapped in a passthrough for tmux
+fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_with_command() {
+        // The command replaces OSC 52, so its failure is the copy's failure
+        assert!(copy("hi", Some("cat")).is_ok());
+        assert!(copy("hi", Some("false")).is_err());
+        assert!(copy("hi", Some("tgcp-no-such-command")).is_err());
+    }
+}
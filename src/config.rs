@@ -3,11 +3,16 @@
 //! Stores user preferences in ~/.config/tgcp/config.yaml (XDG compliant)
 //! Falls back to ~/.tgcp/config.yaml if XDG dirs not available
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::gcp::auth::{CredentialConfig, CredentialSource};
+use crate::gcp::client::{derive_region, parse_region};
+use crate::gcp::gcloud::{self, GcloudConfig};
+
 /// User configuration stored on disk
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
@@ -19,19 +24,195 @@ pub struct Config {
     #[serde(default)]
     pub zone: Option<String>,
 
+    /// Last used region of regional resources, "all" lists every region
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+
     /// Last viewed resource type
     #[serde(default)]
     pub last_resource: Option<String>,
+
+    /// Maximum pages fetched per list call (default: 20)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_pages: Option<usize>,
+
+    /// Maximum concurrent requests of a bulk action (default: 4)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bulk_concurrency: Option<usize>,
+
+    /// Program that receives copied text instead of OSC 52, for terminals without it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clipboard_command: Option<String>,
+
+    /// Zones switched to with the 0-9 keys, in key order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub favorite_zones: Option<Vec<String>>,
+
+    /// Projects pinned to the top of the project picker
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_projects: Vec<String>,
+
+    /// Recently used projects, most recent first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent_projects: Vec<String>,
+
+    /// Service account impersonated without a context, a comma-separated
+    /// list is a delegation chain ending with the target
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonate_service_account: Option<String>,
+
+    /// Named contexts, selected with --context or :ctx
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: BTreeMap<String, Context>,
+
+    /// Context used at startup
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_context: Option<String>,
+
+    /// Context in use this session (--context overrides current_context)
+    #[serde(skip)]
+    pub active_context: Option<String>,
+
+    /// gcloud configurations as contexts, named "gcloud/<name>", or why one can't be used
+    #[serde(skip)]
+    pub gcloud_contexts: BTreeMap<String, Result<Context, String>>,
+
+    /// Active gcloud configuration, used after the config file
+    #[serde(skip)]
+    pub gcloud: Option<GcloudConfig>,
+
+    /// --impersonate-service-account, overrides contexts and the config file
+    #[serde(skip)]
+    pub impersonate_override: Option<String>,
+}
+
+/// Project, location, credentials and safety settings used together
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Context {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+
+    /// "adc" or the path of a credential file, the default chain if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<String>,
+
+    /// Service account impersonated with the credentials, or a
+    /// comma-separated delegation chain ending with it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonate_service_account: Option<String>,
+
+    /// Block all write operations
+    #[serde(default)]
+    pub readonly: bool,
+}
+
+impl Context {
+    /// Credentials of the context for the token provider
+    pub fn credential_config(&self) -> CredentialConfig {
+        let source = match self.credentials.as_deref() {
+            None => CredentialSource::Default,
+            Some("adc") => CredentialSource::Adc,
+            Some(path) => CredentialSource::File(expand_home(path)),
+        };
+        let config = CredentialConfig {
+            source,
+            ..Default::default()
+        };
+        match &self.impersonate_service_account {
+            Some(chain) => config.impersonating(chain),
+            None => config,
+        }
+    }
+
+    /// Context of a gcloud configuration, using the credentials gcloud stored for its account.
+    /// Fails when they are missing rather than acting as another principal.
+    pub fn from_gcloud(config: &GcloudConfig) -> Result<Self> {
+        let credentials = match &config.account {
+            Some(account) => {
+                let Some(path) = config.credentials_file() else {
+                    bail!(
+                        "gcloud configuration '{}' uses account {}, but gcloud has no credentials stored for it, run `gcloud auth login {}`",
+                        config.name,
+                        account,
+                        account
+                    );
+                };
+                Some(path.to_string_lossy().into_owned())
+            }
+            None => None,
+        };
+        Ok(Self {
+            project: config.project.clone(),
+            zone: config.zone.clone(),
+            region: config
+                .region
+                .clone()
+                .or_else(|| config.zone.as_deref().map(derive_region)),
+            credentials,
+            impersonate_service_account: config.impersonate_service_account.clone(),
+            readonly: false,
+        })
+    }
 }
 
+/// Prefix of contexts made from gcloud configurations
+pub const GCLOUD_CONTEXT_PREFIX: &str = "gcloud/";
+
+/// Expand a leading ~ to the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest);
+        }
+    } else if path == "~" {
+        if let Some(home) = dirs::home_dir() {
+            return home;
+        }
+    }
+    PathBuf::from(path)
+}
+
+/// Concurrent requests of a bulk action unless configured
+pub const DEFAULT_BULK_CONCURRENCY: usize = 4;
+
+/// Zones on the 0-5 keys unless configured
+pub const DEFAULT_FAVORITE_ZONES: &[&str] = &[
+    "us-central1-a",
+    "us-east1-b",
+    "us-west1-a",
+    "europe-west1-b",
+    "asia-east1-a",
+    "asia-northeast1-a",
+];
+
+/// Number of zone hotkeys (0-9)
+pub const MAX_FAVORITE_ZONES: usize = 10;
+
+/// Projects remembered as recent
+pub const MAX_RECENT_PROJECTS: usize = 10;
+
 impl Config {
     /// Load config from disk, or return default if not found
     pub fn load() -> Self {
+        let mut config = Self::read();
+        config.active_context = config.current_context.clone();
+        config.set_gcloud_configs(gcloud::active(), gcloud::list());
+        config
+    }
+
+    /// Parse the config file, default if missing or invalid
+    fn read() -> Self {
         let path = Self::config_path();
 
         if path.exists() {
             match fs::read_to_string(&path) {
-                Ok(contents) => match serde_yaml::from_str(&contents) {
+                Ok(contents) => match serde_yaml::from_str::<Config>(&contents) {
                     Ok(config) => return config,
                     Err(e) => {
                         tracing::warn!("Failed to parse config: {}", e);
@@ -63,34 +244,160 @@ impl Config {
     }
 
     /// Get the config file path
-    /// Uses XDG config directory if available, otherwise ~/.tgcp/
     fn config_path() -> PathBuf {
-        // Try XDG config dir first (e.g., ~/.config/tgcp/config.yaml)
+        Self::config_dir().join("config.yaml")
+    }
+
+    /// Get the config directory (also holds user resource definitions)
+    /// Uses XDG config directory if available, otherwise ~/.tgcp/
+    pub fn config_dir() -> PathBuf {
+        // Try XDG config dir first (e.g., ~/.config/tgcp)
         if let Some(config_dir) = dirs::config_dir() {
-            return config_dir.join("tgcp").join("config.yaml");
+            return config_dir.join("tgcp");
         }
 
         // Fallback to home directory
         if let Some(home) = dirs::home_dir() {
-            return home.join(".tgcp").join("config.yaml");
+            return home.join(".tgcp");
         }
 
         // Last resort: current directory
-        PathBuf::from(".tgcp").join("config.yaml")
+        PathBuf::from(".tgcp")
     }
 
-    /// Update project and save
+    /// Update project, remember it as recent and save
     pub fn set_project(&mut self, project: &str) -> Result<()> {
         self.project = Some(project.to_string());
+        self.recent_projects.retain(|p| p != project);
+        self.recent_projects.insert(0, project.to_string());
+        self.recent_projects.truncate(MAX_RECENT_PROJECTS);
         self.save()
     }
 
+    /// Pin or unpin a project and save, returns whether it is now pinned
+    pub fn toggle_pinned_project(&mut self, project: &str) -> Result<bool> {
+        let pinned = if self.pinned_projects.iter().any(|p| p == project) {
+            self.pinned_projects.retain(|p| p != project);
+            false
+        } else {
+            self.pinned_projects.push(project.to_string());
+            true
+        };
+        self.save()?;
+        Ok(pinned)
+    }
+
     /// Update zone and save
     pub fn set_zone(&mut self, zone: &str) -> Result<()> {
         self.zone = Some(zone.to_string());
         self.save()
     }
 
+    /// Update region and save
+    pub fn set_region(&mut self, region: &str) -> Result<()> {
+        self.region = Some(region.to_string());
+        self.save()
+    }
+
+    /// Use the active gcloud configuration as a layer and list all as contexts
+    pub fn set_gcloud_configs(&mut self, active: Option<GcloudConfig>, all: Vec<GcloudConfig>) {
+        self.gcloud_contexts = all
+            .iter()
+            .map(|c| {
+                (
+                    format!("{}{}", GCLOUD_CONTEXT_PREFIX, c.name),
+                    Context::from_gcloud(c).map_err(|e| e.to_string()),
+                )
+            })
+            .collect();
+        self.gcloud = active;
+    }
+
+    /// Use a gcloud configuration as the context for this session (--gcloud-config)
+    pub fn select_gcloud_config(&mut self, name: &str) -> Result<()> {
+        self.select_context(&format!("{}{}", GCLOUD_CONTEXT_PREFIX, name))
+    }
+
+    /// Names of the config file contexts, then the gcloud ones
+    pub fn context_names(&self) -> Vec<&str> {
+        self.contexts
+            .keys()
+            .chain(self.gcloud_contexts.keys())
+            .map(|k| k.as_str())
+            .collect()
+    }
+
+    /// Use a context for this session
+    pub fn select_context(&mut self, name: &str) -> Result<()> {
+        if !self.contexts.contains_key(name) && !self.gcloud_contexts.contains_key(name) {
+            let known = self.context_names();
+            if known.is_empty() {
+                bail!(
+                    "Unknown context '{}', none are defined in the config file or by gcloud",
+                    name
+                );
+            }
+            bail!("Unknown context '{}', defined: {}", name, known.join(", "));
+        }
+        let previous = self.active_context.replace(name.to_string());
+        if let Err(e) = self.check_active_context() {
+            self.active_context = previous;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Fail if the active context is a gcloud configuration without stored credentials,
+    /// e.g. the saved one after `gcloud auth revoke`
+    pub fn check_active_context(&self) -> Result<()> {
+        let name = self.active_context.as_deref().unwrap_or_default();
+        if let Some(Err(e)) = self.gcloud_contexts.get(name) {
+            bail!("{}", e);
+        }
+        Ok(())
+    }
+
+    /// Use a context now and at the next start
+    pub fn use_context(&mut self, name: &str) -> Result<()> {
+        self.select_context(name)?;
+        self.current_context = Some(name.to_string());
+        self.save()
+    }
+
+    /// Context in use, if any
+    pub fn context(&self) -> Option<&Context> {
+        let name = self.active_context.as_deref()?;
+        self.contexts
+            .get(name)
+            .or_else(|| self.gcloud_contexts.get(name)?.as_ref().ok())
+    }
+
+    /// Credentials of the active context, the default chain without one,
+    /// impersonating the config file's or else the active gcloud configuration's account;
+    /// --impersonate-service-account replaces the impersonated account
+    pub fn credential_config(&self) -> CredentialConfig {
+        let impersonate = self
+            .impersonate_service_account
+            .as_ref()
+            .or_else(|| self.gcloud.as_ref()?.impersonate_service_account.as_ref());
+        let config = match self.context() {
+            Some(context) => context.credential_config(),
+            None => match impersonate {
+                Some(chain) => CredentialConfig::default().impersonating(chain),
+                None => CredentialConfig::default(),
+            },
+        };
+        match &self.impersonate_override {
+            Some(chain) => config.impersonating(chain),
+            None => config,
+        }
+    }
+
+    /// True if the active context is read-only
+    pub fn context_readonly(&self) -> bool {
+        self.context().is_some_and(|c| c.readonly)
+    }
+
     /// Update last resource and save
     #[allow(dead_code)]
     pub fn set_last_resource(&mut self, resource: &str) -> Result<()> {
@@ -98,24 +405,65 @@ impl Config {
         self.save()
     }
 
-    /// Get effective project (env -> config -> None)
+    /// Get effective project (context -> env -> gcloud -> config -> None);
+    /// `TokenProvider::get_project` continues with the credentials
     pub fn effective_project(&self) -> Option<String> {
-        // Priority: 1. Environment variable, 2. Config file
-        std::env::var("GCP_PROJECT")
-            .ok()
+        // Priority: 1. Context, 2. Environment variable, 3. gcloud, 4. Config file (last used)
+        self.context()
+            .and_then(|c| c.project.clone())
+            .or_else(|| std::env::var("GCP_PROJECT").ok())
             .or_else(|| std::env::var("GOOGLE_CLOUD_PROJECT").ok())
             .or_else(|| std::env::var("GCLOUD_PROJECT").ok())
+            .or_else(|| self.gcloud.as_ref()?.project.clone())
             .or_else(|| self.project.clone())
     }
 
-    /// Get effective zone (env -> config -> default)
+    /// Get effective zone (context -> env -> gcloud -> config -> default)
     pub fn effective_zone(&self) -> String {
-        // Priority: 1. Environment variable, 2. Config file, 3. Default
-        std::env::var("CLOUDSDK_COMPUTE_ZONE")
-            .ok()
+        // Priority: 1. Context, 2. Environment variable, 3. gcloud, 4. Config file, 5. Default
+        self.context()
+            .and_then(|c| c.zone.clone())
+            .or_else(|| std::env::var("CLOUDSDK_COMPUTE_ZONE").ok())
+            .or_else(|| self.gcloud.as_ref()?.zone.clone())
             .or_else(|| self.zone.clone())
             .unwrap_or_else(|| "us-central1-a".to_string())
     }
+
+    /// Get effective region (context -> env -> gcloud -> config -> region of the zone)
+    pub fn effective_region(&self, zone: &str) -> String {
+        self.context()
+            .and_then(|c| c.region.clone())
+            .or_else(|| std::env::var("CLOUDSDK_COMPUTE_REGION").ok())
+            .or_else(|| self.gcloud.as_ref()?.region.clone())
+            .or_else(|| self.region.clone())
+            .map(|r| parse_region(&r))
+            .unwrap_or_else(|| derive_region(zone))
+    }
+
+    /// Get effective page cap for list calls (config -> default)
+    pub fn effective_max_pages(&self) -> usize {
+        self.max_pages
+            .filter(|&n| n > 0)
+            .unwrap_or(crate::gcp::client::DEFAULT_MAX_PAGES)
+    }
+
+    /// Get zones of the 0-9 keys (config -> default)
+    pub fn effective_favorite_zones(&self) -> Vec<String> {
+        match &self.favorite_zones {
+            Some(zones) => zones.iter().take(MAX_FAVORITE_ZONES).cloned().collect(),
+            None => DEFAULT_FAVORITE_ZONES
+                .iter()
+                .map(|z| z.to_string())
+                .collect(),
+        }
+    }
+
+    /// Get effective concurrency for bulk actions (config -> default)
+    pub fn effective_bulk_concurrency(&self) -> usize {
+        self.bulk_concurrency
+            .filter(|&n| n > 0)
+            .unwrap_or(DEFAULT_BULK_CONCURRENCY)
+    }
 }
 
 #[cfg(test)]
@@ -134,7 +482,24 @@ mod tests {
         let config = Config {
             project: Some("my-project".to_string()),
             zone: Some("us-central1-a".to_string()),
+            region: Some("all".to_string()),
             last_resource: Some("vm-instances".to_string()),
+            max_pages: Some(5),
+            bulk_concurrency: Some(8),
+            clipboard_command: Some("xclip -selection clipboard".to_string()),
+            favorite_zones: Some(vec!["europe-west4-a".to_string()]),
+            pinned_projects: vec!["prod".to_string()],
+            recent_projects: vec!["dev".to_string(), "prod".to_string()],
+            contexts: BTreeMap::from([(
+                "prod".to_string(),
+                Context {
+                    project: Some("acme-prod".to_string()),
+                    readonly: true,
+                    ..Default::default()
+                },
+            )]),
+            current_context: Some("prod".to_string()),
+            ..Default::default()
         };
 
         let yaml = serde_yaml::to_string(&config).unwrap();
@@ -142,6 +507,206 @@ mod tests {
 
         assert_eq!(parsed.project, config.project);
         assert_eq!(parsed.zone, config.zone);
+        assert_eq!(parsed.region, config.region);
         assert_eq!(parsed.last_resource, config.last_resource);
+        assert_eq!(parsed.max_pages, config.max_pages);
+        assert_eq!(parsed.bulk_concurrency, config.bulk_concurrency);
+        assert_eq!(parsed.clipboard_command, config.clipboard_command);
+        assert_eq!(parsed.favorite_zones, config.favorite_zones);
+        assert_eq!(parsed.pinned_projects, config.pinned_projects);
+        assert_eq!(parsed.recent_projects, config.recent_projects);
+        assert_eq!(parsed.contexts, config.contexts);
+        assert_eq!(parsed.current_context, config.current_context);
+    }
+
+    #[test]
+    fn test_effective_max_pages() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.effective_max_pages(),
+            crate::gcp::client::DEFAULT_MAX_PAGES
+        );
+
+        config.max_pages = Some(3);
+        assert_eq!(config.effective_max_pages(), 3);
+
+        // Zero would never fetch anything, fall back to the default
+        config.max_pages = Some(0);
+        assert_eq!(
+            config.effective_max_pages(),
+            crate::gcp::client::DEFAULT_MAX_PAGES
+        );
+    }
+
+    #[test]
+    fn test_effective_favorite_zones() {
+        let mut config = Config::default();
+        assert_eq!(config.effective_favorite_zones()[0], "us-central1-a");
+
+        config.favorite_zones = Some((0..12).map(|i| format!("zone-{}", i)).collect());
+        let zones = config.effective_favorite_zones();
+        assert_eq!(zones.len(), MAX_FAVORITE_ZONES);
+        assert_eq!(zones[9], "zone-9");
+
+        // An empty list disables the hotkeys
+        config.favorite_zones = Some(Vec::new());
+        assert!(config.effective_favorite_zones().is_empty());
+    }
+
+    #[test]
+    fn test_contexts() {
+        let yaml = r#"
+project: dev-project
+zone: us-central1-a
+contexts:
+  prod:
+    project: acme-prod
+    zone: europe-west1-b
+    credentials: /keys/prod.json
+    impersonate_service_account: viewer@acme-prod.iam.gserviceaccount.com
+    readonly: true
+  staging:
+    project: acme-staging
+    credentials: adc
+"#;
+        let mut config: Config = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.context().is_none());
+        assert_eq!(config.credential_config(), CredentialConfig::default());
+
+        assert!(config.select_context("qa").is_err());
+        config.select_context("prod").unwrap();
+        assert_eq!(config.effective_project().as_deref(), Some("acme-prod"));
+        assert_eq!(config.effective_zone(), "europe-west1-b");
+        assert_eq!(config.effective_region("europe-west1-b"), "europe-west1");
+        assert!(config.context_readonly());
+        assert_eq!(
+            config.credential_config(),
+            CredentialConfig {
+                source: CredentialSource::File(PathBuf::from("/keys/prod.json")),
+                impersonate_service_account: Some(
+                    "viewer@acme-prod.iam.gserviceaccount.com".to_string()
+                ),
+                delegates: Vec::new(),
+            }
+        );
+
+        config.select_context("staging").unwrap();
+        assert!(!config.context_readonly());
+        assert_eq!(config.credential_config().source, CredentialSource::Adc);
+    }
+
+    #[test]
+    fn test_gcloud_configs() {
+        let gcloud_config = |name: &str, project: &str, zone: &str| GcloudConfig {
+            name: name.to_string(),
+            project: Some(project.to_string()),
+            zone: Some(zone.to_string()),
+            ..Default::default()
+        };
+        let active = gcloud_config("default", "gcloud-project", "asia-east1-a");
+        let dev = gcloud_config("dev", "acme-dev", "europe-west1-b");
+        let orphan = GcloudConfig {
+            account: Some("nobody@example.invalid".to_string()),
+            ..gcloud_config("orphan", "acme-qa", "us-east1-b")
+        };
+
+        let mut config = Config::default();
+        config.set_gcloud_configs(Some(active.clone()), vec![active, dev, orphan]);
+        assert_eq!(
+            config.effective_project().as_deref(),
+            Some("gcloud-project")
+        );
+        assert_eq!(config.effective_zone(), "asia-east1-a");
+        assert_eq!(config.effective_region("asia-east1-a"), "asia-east1");
+
+        // The active gcloud configuration comes before the last used values
+        config.project = Some("last-used".to_string());
+        config.zone = Some("us-east1-b".to_string());
+        assert_eq!(
+            config.effective_project().as_deref(),
+            Some("gcloud-project")
+        );
+        assert_eq!(config.effective_zone(), "asia-east1-a");
+        config.gcloud = None;
+        assert_eq!(config.effective_project().as_deref(), Some("last-used"));
+
+        // Every gcloud configuration is a context
+        assert_eq!(
+            config.context_names(),
+            ["gcloud/default", "gcloud/dev", "gcloud/orphan"]
+        );
+        assert!(config.select_gcloud_config("qa").is_err());
+
+        // No stored credentials for the account, rather than another principal
+        let err = config.select_gcloud_config("orphan").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("gcloud auth login nobody@example.invalid"));
+        assert_eq!(config.active_context, None);
+
+        config.select_gcloud_config("dev").unwrap();
+        assert_eq!(config.active_context.as_deref(), Some("gcloud/dev"));
+        assert_eq!(config.effective_project().as_deref(), Some("acme-dev"));
+        assert_eq!(config.effective_region("europe-west1-b"), "europe-west1");
+        assert!(!config.context_readonly());
+    }
+
+    #[test]
+    fn test_impersonation() {
+        let yaml = r#"
+impersonate_service_account: hop@acme.iam.gserviceaccount.com,viewer@acme.iam.gserviceaccount.com
+contexts:
+  prod:
+    credentials: adc
+"#;
+        let mut config: Config = serde_yaml::from_str(yaml).unwrap();
+        let credentials = config.credential_config();
+        assert_eq!(
+            credentials.impersonate_service_account.as_deref(),
+            Some("viewer@acme.iam.gserviceaccount.com")
+        );
+        assert_eq!(credentials.delegates, ["hop@acme.iam.gserviceaccount.com"]);
+
+        // Contexts bring their own impersonation
+        config.select_context("prod").unwrap();
+        assert_eq!(config.credential_config().impersonate_service_account, None);
+
+        // The command line flag wins over both
+        config.impersonate_override = Some("admin@acme.iam.gserviceaccount.com".to_string());
+        let credentials = config.credential_config();
+        assert_eq!(credentials.source, CredentialSource::Adc);
+        assert_eq!(
+            credentials.impersonate_service_account.as_deref(),
+            Some("admin@acme.iam.gserviceaccount.com")
+        );
+        assert!(credentials.delegates.is_empty());
+    }
+
+    #[test]
+    fn test_gcloud_impersonation() {
+        let active = GcloudConfig {
+            name: "default".to_string(),
+            impersonate_service_account: Some("viewer@acme.iam.gserviceaccount.com".to_string()),
+            ..Default::default()
+        };
+        let mut config = Config::default();
+        config.set_gcloud_configs(Some(active), Vec::new());
+        assert_eq!(
+            config
+                .credential_config()
+                .impersonate_service_account
+                .as_deref(),
+            Some("viewer@acme.iam.gserviceaccount.com")
+        );
+
+        // The config file comes first
+        config.impersonate_service_account = Some("admin@acme.iam.gserviceaccount.com".to_string());
+        assert_eq!(
+            config
+                .credential_config()
+                .impersonate_service_account
+                .as_deref(),
+            Some("admin@acme.iam.gserviceaccount.com")
+        );
     }
 }
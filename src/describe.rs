@@ -0,0 +1,701 @@
+//! Describe view document model
+//!
+//! Lays out a JSON value as JSON or YAML lines, each tied to the JSON pointer
+//! of the node it shows, so the view can fold nodes, search and copy paths.
+
+use serde_json::Value;
+use std::cell::Cell;
+use std::collections::HashSet;
+
+/// Syntax the document is shown in
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DocFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+impl DocFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            DocFormat::Json => "JSON",
+            DocFormat::Yaml => "YAML",
+        }
+    }
+}
+
+/// Syntax class of a line fragment, used for highlighting
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    Key,
+    String,
+    Number,
+    Bool,
+    Null,
+    Punct,
+    Comment,
+}
+
+/// Single rendered line of the document
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocLine {
+    /// Leading spaces
+    pub indent: usize,
+    pub tokens: Vec<(String, TokenKind)>,
+    /// JSON pointer of the node this line belongs to ("" for the root)
+    pub pointer: String,
+    /// True if the line opens an object or array that can be folded
+    pub foldable: bool,
+    pub collapsed: bool,
+}
+
+impl DocLine {
+    /// Plain text of the line including indentation
+    pub fn text(&self) -> String {
+        let mut text = " ".repeat(self.indent);
+        for (s, _) in &self.tokens {
+            text.push_str(s);
+        }
+        text
+    }
+}
+
+/// Lay out a value as lines, skipping the children of collapsed nodes
+pub fn build_lines(value: &Value, format: DocFormat, collapsed: &HashSet<String>) -> Vec<DocLine> {
+    let mut lines = Vec::new();
+    match format {
+        DocFormat::Json => json_lines(value, None, "", 0, true, collapsed, &mut lines),
+        DocFormat::Yaml => yaml_lines(value, YamlKey::Root, "", 0, collapsed, &mut lines),
+    }
+    lines
+}
+
+/// Escape a key for use in a JSON pointer (RFC 6901)
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Parent pointer, None for the root
+pub fn parent_pointer(pointer: &str) -> Option<&str> {
+    pointer.rfind('/').map(|i| &pointer[..i])
+}
+
+fn child_count(value: &Value) -> Option<usize> {
+    match value {
+        Value::Object(map) => Some(map.len()),
+        Value::Array(arr) => Some(arr.len()),
+        _ => None,
+    }
+}
+
+fn children(value: &Value, pointer: &str) -> Vec<(String, String, Value)> {
+    match value {
+        Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| {
+                (
+                    k.clone(),
+                    format!("{}/{}", pointer, escape_pointer(k)),
+                    v.clone(),
+                )
+            })
+            .collect(),
+        Value::Array(arr) => arr
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i.to_string(), format!("{}/{}", pointer, i), v.clone()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn summary(value: &Value) -> String {
+    match value {
+        Value::Object(map) => format!("  {} keys", map.len()),
+        Value::Array(arr) => format!("  {} items", arr.len()),
+        _ => String::new(),
+    }
+}
+
+fn scalar_kind(value: &Value) -> TokenKind {
+    match value {
+        Value::String(_) => TokenKind::String,
+        Value::Number(_) => TokenKind::Number,
+        Value::Bool(_) => TokenKind::Bool,
+        _ => TokenKind::Null,
+    }
+}
+
+fn json_lines(
+    value: &Value,
+    key: Option<&str>,
+    pointer: &str,
+    depth: usize,
+    last: bool,
+    collapsed: &HashSet<String>,
+    out: &mut Vec<DocLine>,
+) {
+    let indent = depth * 2;
+    let comma = if last { "" } else { "," };
+    let mut tokens = Vec::new();
+    if let Some(key) = key {
+        tokens.push((Value::String(key.to_string()).to_string(), TokenKind::Key));
+        tokens.push((": ".to_string(), TokenKind::Punct));
+    }
+
+    let (open, close) = match value {
+        Value::Object(_) => ("{", "}"),
+        Value::Array(_) => ("[", "]"),
+        scalar => {
+            tokens.push((scalar.to_string(), scalar_kind(scalar)));
+            tokens.push((comma.to_string(), TokenKind::Punct));
+            out.push(DocLine {
+                indent,
+                tokens,
+                pointer: pointer.to_string(),
+                foldable: false,
+                collapsed: false,
+            });
+            return;
+        }
+    };
+
+    if child_count(value) == Some(0) {
+        tokens.push((format!("{}{}{}", open, close, comma), TokenKind::Punct));
+        out.push(DocLine {
+            indent,
+            tokens,
+            pointer: pointer.to_string(),
+            foldable: false,
+            collapsed: false,
+        });
+        return;
+    }
+
+    if collapsed.contains(pointer) {
+        tokens.push((format!("{}…{}{}", open, close, comma), TokenKind::Punct));
+        tokens.push((summary(value), TokenKind::Comment));
+        out.push(DocLine {
+            indent,
+            tokens,
+            pointer: pointer.to_string(),
+            foldable: true,
+            collapsed: true,
+        });
+        return;
+    }
+
+    tokens.push((open.to_string(), TokenKind::Punct));
+    out.push(DocLine {
+        indent,
+        tokens,
+        pointer: pointer.to_string(),
+        foldable: true,
+        collapsed: false,
+    });
+
+    let items = children(value, pointer);
+    let count = items.len();
+    let is_object = value.is_object();
+    for (i, (name, child_pointer, child)) in items.iter().enumerate() {
+        let child_key = is_object.then_some(name.as_str());
+        json_lines(
+            child,
+            child_key,
+            child_pointer,
+            depth + 1,
+            i + 1 == count,
+            collapsed,
+            out,
+        );
+    }
+
+    out.push(DocLine {
+        indent,
+        tokens: vec![(format!("{}{}", close, comma), TokenKind::Punct)],
+        pointer: pointer.to_string(),
+        foldable: false,
+        collapsed: false,
+    });
+}
+
+/// Position of a node in its YAML parent
+enum YamlKey<'a> {
+    Root,
+    Map(&'a str),
+    Seq(usize),
+}
+
+/// Format a scalar as a single YAML line
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        // Multi-line strings use the double-quoted style to stay on one line
+        Value::String(s) if s.contains('\n') => Value::String(s.clone()).to_string(),
+        _ => serde_yaml::to_string(value)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_else(|_| value.to_string()),
+    }
+}
+
+fn yaml_lines(
+    value: &Value,
+    key: YamlKey,
+    pointer: &str,
+    depth: usize,
+    collapsed: &HashSet<String>,
+    out: &mut Vec<DocLine>,
+) {
+    let is_compound = child_count(value).is_some_and(|n| n > 0);
+
+    // The root compound has no line of its own, its children start at column 0
+    if let YamlKey::Root = key {
+        if !is_compound {
+            out.push(DocLine {
+                indent: 0,
+                tokens: vec![(yaml_scalar(value), scalar_kind(value))],
+                pointer: pointer.to_string(),
+                foldable: false,
+                collapsed: false,
+            });
+            return;
+        }
+        yaml_children(value, pointer, 0, collapsed, out);
+        return;
+    }
+
+    let mut tokens = Vec::new();
+    match key {
+        YamlKey::Map(name) => {
+            tokens.push((
+                yaml_scalar(&Value::String(name.to_string())),
+                TokenKind::Key,
+            ));
+            tokens.push((":".to_string(), TokenKind::Punct));
+        }
+        YamlKey::Seq(_) => tokens.push(("-".to_string(), TokenKind::Punct)),
+        YamlKey::Root => unreachable!(),
+    }
+
+    if !is_compound {
+        let text = match value {
+            Value::Object(_) => "{}".to_string(),
+            Value::Array(_) => "[]".to_string(),
+            scalar => yaml_scalar(scalar),
+        };
+        tokens.push((format!(" {}", text), scalar_kind(value)));
+        out.push(DocLine {
+            indent: depth * 2,
+            tokens,
+            pointer: pointer.to_string(),
+            foldable: false,
+            collapsed: false,
+        });
+        return;
+    }
+
+    let is_collapsed = collapsed.contains(pointer);
+    if is_collapsed {
+        let placeholder = if value.is_object() {
+            " {…}"
+        } else {
+            " […]"
+        };
+        tokens.push((placeholder.to_string(), TokenKind::Punct));
+    }
+    // Sequence entries get a comment with their index so they have a line to fold
+    if let YamlKey::Seq(index) = key {
+        tokens.push((format!(" # {}", index), TokenKind::Comment));
+    }
+    if is_collapsed {
+        tokens.push((summary(value), TokenKind::Comment));
+    }
+
+    out.push(DocLine {
+        indent: depth * 2,
+        tokens,
+        pointer: pointer.to_string(),
+        foldable: true,
+        collapsed: is_collapsed,
+    });
+
+    if !is_collapsed {
+        yaml_children(value, pointer, depth + 1, collapsed, out);
+    }
+}
+
+fn yaml_children(
+    value: &Value,
+    pointer: &str,
+    depth: usize,
+    collapsed: &HashSet<String>,
+    out: &mut Vec<DocLine>,
+) {
+    let is_object = value.is_object();
+    for (i, (name, child_pointer, child)) in children(value, pointer).iter().enumerate() {
+        let key = if is_object {
+            YamlKey::Map(name)
+        } else {
+            YamlKey::Seq(i)
+        };
+        yaml_lines(child, key, child_pointer, depth, collapsed, out);
+    }
+}
+
+/// Navigation, folding and search state of the describe view
+#[derive(Debug, Clone, Default)]
+pub struct DescribeView {
+    pub format: DocFormat,
+    /// Pointers of folded nodes
+    pub collapsed: HashSet<String>,
+    /// Index of the selected line
+    pub cursor: usize,
+    /// Index of the first visible line
+    pub scroll: usize,
+    /// Visible line count, updated by the renderer
+    pub viewport: Cell<usize>,
+    /// Last search query
+    pub search: String,
+    /// True while the search query is being typed
+    pub search_input: bool,
+    /// Pointers of nodes whose line matches the search, in document order
+    pub matches: Vec<String>,
+    /// Index into `matches` of the last jump
+    pub current_match: Option<usize>,
+}
+
+impl DescribeView {
+    pub fn lines(&self, value: &Value) -> Vec<DocLine> {
+        build_lines(value, self.format, &self.collapsed)
+    }
+
+    /// Pointer of the selected line
+    pub fn selected_pointer(&self, value: &Value) -> Option<String> {
+        self.lines(value)
+            .get(self.cursor)
+            .map(|line| line.pointer.clone())
+    }
+
+    pub fn move_cursor(&mut self, value: &Value, delta: isize) {
+        let count = self.lines(value).len();
+        let target = self.cursor as isize + delta;
+        self.cursor = target.clamp(0, count.saturating_sub(1) as isize) as usize;
+        self.ensure_visible();
+    }
+
+    pub fn go_to_top(&mut self) {
+        self.cursor = 0;
+        self.ensure_visible();
+    }
+
+    pub fn go_to_bottom(&mut self, value: &Value) {
+        self.cursor = self.lines(value).len().saturating_sub(1);
+        self.ensure_visible();
+    }
+
+    /// Fold or unfold the node of the selected line
+    pub fn toggle_fold(&mut self, value: &Value) {
+        let Some(pointer) = self.selected_pointer(value) else {
+            return;
+        };
+        if !self.collapsed.remove(&pointer) && is_foldable(value, &pointer) {
+            self.collapsed.insert(pointer.clone());
+        }
+        self.select_pointer(value, &pointer);
+    }
+
+    /// Fold the selected node, or move to its parent if it is already folded or a leaf
+    pub fn collapse(&mut self, value: &Value) {
+        let Some(pointer) = self.selected_pointer(value) else {
+            return;
+        };
+        if !self.collapsed.contains(&pointer) && is_foldable(value, &pointer) {
+            self.collapsed.insert(pointer.clone());
+            self.select_pointer(value, &pointer);
+        } else if let Some(parent) = parent_pointer(&pointer) {
+            let parent = parent.to_string();
+            self.select_pointer(value, &parent);
+        }
+    }
+
+    /// Unfold the selected node
+    pub fn expand(&mut self, value: &Value) {
+        if let Some(pointer) = self.selected_pointer(value) {
+            if self.collapsed.remove(&pointer) {
+                self.select_pointer(value, &pointer);
+            }
+        }
+    }
+
+    /// Fold every object and array below the top level
+    pub fn collapse_all(&mut self, value: &Value) {
+        let pointer = self.selected_pointer(value);
+        self.collapsed = build_lines(value, self.format, &HashSet::new())
+            .into_iter()
+            .filter(|line| line.foldable && line.pointer.matches('/').count() >= 1)
+            .map(|line| line.pointer)
+            .collect();
+        // Keep the cursor on the top-level node containing the old selection
+        if let Some(pointer) = pointer {
+            let top = top_level(&pointer).to_string();
+            self.select_pointer(value, &top);
+        }
+    }
+
+    pub fn expand_all(&mut self, value: &Value) {
+        let pointer = self.selected_pointer(value);
+        self.collapsed.clear();
+        if let Some(pointer) = pointer {
+            self.select_pointer(value, &pointer);
+        }
+    }
+
+    /// Switch between JSON and YAML, keeping the selected node
+    pub fn toggle_format(&mut self, value: &Value) {
+        let pointer = self.selected_pointer(value);
+        self.format = match self.format {
+            DocFormat::Json => DocFormat::Yaml,
+            DocFormat::Yaml => DocFormat::Json,
+        };
+        self.update_matches(value);
+        if let Some(pointer) = pointer {
+            self.select_pointer(value, &pointer);
+        }
+    }
+
+    /// Search for a query (case-insensitive) and jump to the first match after the cursor
+    pub fn search(&mut self, value: &Value, query: &str) {
+        self.search = query.to_string();
+        self.update_matches(value);
+        self.current_match = None;
+        self.next_match(value, true);
+    }
+
+    /// Recompute matches over the fully expanded document
+    fn update_matches(&mut self, value: &Value) {
+        self.matches.clear();
+        self.current_match = None;
+        if self.search.is_empty() {
+            return;
+        }
+        let query = self.search.to_lowercase();
+        for line in build_lines(value, self.format, &HashSet::new()) {
+            if line.text().to_lowercase().contains(&query)
+                && self.matches.last() != Some(&line.pointer)
+            {
+                self.matches.push(line.pointer);
+            }
+        }
+    }
+
+    /// Jump to the next (or previous) match, unfolding the nodes around it
+    pub fn next_match(&mut self, value: &Value, forward: bool) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let count = self.matches.len();
+        let index = match self.current_match {
+            Some(i) if forward => (i + 1) % count,
+            Some(i) => (i + count - 1) % count,
+            None => {
+                // First jump: closest match from the cursor in the search direction
+                let lines = build_lines(value, self.format, &HashSet::new());
+                let cursor_pointer = self.selected_pointer(value).unwrap_or_default();
+                let cursor_index = lines
+                    .iter()
+                    .position(|l| l.pointer == cursor_pointer)
+                    .unwrap_or(0);
+                let positions: Vec<usize> = self
+                    .matches
+                    .iter()
+                    .map(|p| lines.iter().position(|l| &l.pointer == p).unwrap_or(0))
+                    .collect();
+                if forward {
+                    positions
+                        .iter()
+                        .position(|&p| p >= cursor_index)
+                        .unwrap_or(0)
+                } else {
+                    positions
+                        .iter()
+                        .rposition(|&p| p <= cursor_index)
+                        .unwrap_or(count - 1)
+                }
+            }
+        };
+        self.current_match = Some(index);
+
+        let pointer = self.matches[index].clone();
+        self.collapsed
+            .retain(|p| !(p.is_empty() || pointer.starts_with(&format!("{}/", p))));
+        self.select_pointer(value, &pointer);
+    }
+
+    /// Move the cursor to the first line of a node
+    fn select_pointer(&mut self, value: &Value, pointer: &str) {
+        if let Some(index) = self.lines(value).iter().position(|l| l.pointer == pointer) {
+            self.cursor = index;
+        }
+        self.ensure_visible();
+    }
+
+    /// Keep the document in range after it changed (e.g., the full resource arrived)
+    pub fn clamp(&mut self, value: &Value) {
+        let count = self.lines(value).len();
+        self.cursor = self.cursor.min(count.saturating_sub(1));
+        self.update_matches(value);
+        self.ensure_visible();
+    }
+
+    /// Scroll so the cursor is inside the viewport
+    fn ensure_visible(&mut self) {
+        let viewport = self.viewport.get().max(1);
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + viewport {
+            self.scroll = self.cursor + 1 - viewport;
+        }
+    }
+}
+
+/// True if the node at a pointer is a non-empty object or array
+fn is_foldable(value: &Value, pointer: &str) -> bool {
+    value
+        .pointer(pointer)
+        .and_then(child_count)
+        .is_some_and(|n| n > 0)
+}
+
+/// Top-level ancestor of a pointer (e.g., "/spec" for "/spec/template/x")
+fn top_level(pointer: &str) -> &str {
+    match pointer[1.min(pointer.len())..].find('/') {
+        Some(i) => &pointer[..i + 1],
+        None => pointer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn doc() -> Value {
+        json!({
+            "name": "vm-1",
+            "disks": [{"boot": true, "sizeGb": "10"}],
+            "labels": {"a/b": "x"},
+            "tags": []
+        })
+    }
+
+    fn texts(lines: &[DocLine]) -> Vec<String> {
+        lines.iter().map(|l| l.text()).collect()
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let lines = build_lines(&doc(), DocFormat::Json, &HashSet::new());
+        assert_eq!(
+            texts(&lines),
+            vec![
+                "{",
+                "  \"disks\": [",
+                "    {",
+                "      \"boot\": true,",
+                "      \"sizeGb\": \"10\"",
+                "    }",
+                "  ],",
+                "  \"labels\": {",
+                "    \"a/b\": \"x\"",
+                "  },",
+                "  \"name\": \"vm-1\",",
+                "  \"tags\": []",
+                "}",
+            ]
+        );
+        assert_eq!(lines[3].pointer, "/disks/0/boot");
+        assert_eq!(lines[8].pointer, "/labels/a~1b");
+
+        // The output is valid JSON
+        let text = texts(&lines).join("\n");
+        assert_eq!(serde_json::from_str::<Value>(&text).unwrap(), doc());
+    }
+
+    #[test]
+    fn test_yaml_lines() {
+        let lines = build_lines(&doc(), DocFormat::Yaml, &HashSet::new());
+        assert_eq!(
+            texts(&lines),
+            vec![
+                "disks:",
+                "  - # 0",
+                "    boot: true",
+                "    sizeGb: '10'",
+                "labels:",
+                "  a/b: x",
+                "name: vm-1",
+                "tags: []",
+            ]
+        );
+
+        // The output is valid YAML describing the same document
+        let text = texts(&lines).join("\n");
+        assert_eq!(serde_yaml::from_str::<Value>(&text).unwrap(), doc());
+    }
+
+    #[test]
+    fn test_folding() {
+        let value = doc();
+        let mut view = DescribeView::default();
+        view.viewport.set(10);
+
+        // Fold "disks" from its first line
+        view.move_cursor(&value, 1);
+        view.toggle_fold(&value);
+        let lines = view.lines(&value);
+        assert_eq!(lines[1].text(), "  \"disks\": […],  1 items");
+        assert!(lines[1].collapsed);
+        assert_eq!(lines.len(), 8);
+
+        // h on a folded node moves to its parent, l unfolds
+        view.collapse(&value);
+        assert_eq!(view.cursor, 0);
+        view.move_cursor(&value, 1);
+        view.expand(&value);
+        assert_eq!(view.lines(&value).len(), 13);
+
+        view.collapse_all(&value);
+        assert_eq!(view.lines(&value).len(), 6);
+        view.expand_all(&value);
+        assert_eq!(view.lines(&value).len(), 13);
+    }
+
+    #[test]
+    fn test_search_unfolds_and_keeps_pointer_across_formats() {
+        let value = doc();
+        let mut view = DescribeView::default();
+        view.viewport.set(10);
+        view.collapse_all(&value);
+
+        view.search(&value, "SIZEGB");
+        assert_eq!(view.matches, vec!["/disks/0/sizeGb"]);
+        assert_eq!(
+            view.selected_pointer(&value).as_deref(),
+            Some("/disks/0/sizeGb")
+        );
+
+        view.toggle_format(&value);
+        assert_eq!(view.format, DocFormat::Yaml);
+        assert_eq!(
+            view.selected_pointer(&value).as_deref(),
+            Some("/disks/0/sizeGb")
+        );
+        assert_eq!(view.lines(&value)[view.cursor].text(), "    sizeGb: '10'");
+    }
+
+    #[test]
+    fn test_top_level() {
+        assert_eq!(top_level("/spec/template/x"), "/spec");
+        assert_eq!(top_level("/spec"), "/spec");
+        assert_eq!(top_level(""), "");
+    }
+}
@@ -0,0 +1,414 @@
+//! Rendering tables for output outside the TUI
+//!
+//! Shared by the headless `list` output and the `:export` command. Rendered
+//! output goes through the resource's columns, the same cells the table shows;
+//! raw output keeps the items as the API returned them.
+
+use anyhow::{bail, Result};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+use crate::config::expand_home;
+use crate::resource::registry::{extract_json_value, ColumnDef, ResourceDef};
+
+/// File format of an export
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Yaml,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            "yaml" | "yml" => Ok(ExportFormat::Yaml),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            _ => bail!("Unknown format '{}', expected csv, json, yaml or md", name),
+        }
+    }
+
+    /// Format implied by the file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?;
+        Self::parse(ext).ok()
+    }
+}
+
+/// Where the exported items came from
+#[derive(Debug, Clone, Default)]
+pub struct ExportMeta {
+    pub resource: String,
+    pub breadcrumb: Vec<String>,
+    pub project: String,
+    pub zone: String,
+    pub region: String,
+    /// Local and server-side filters in effect
+    pub filters: Vec<String>,
+    pub exported_at: String,
+}
+
+impl ExportMeta {
+    fn entries(&self) -> Vec<(&'static str, String)> {
+        let mut entries = vec![
+            ("resource", self.resource.clone()),
+            ("path", self.breadcrumb.join(" > ")),
+            ("project", self.project.clone()),
+            ("zone", self.zone.clone()),
+            ("region", self.region.clone()),
+        ];
+        if !self.filters.is_empty() {
+            entries.push(("filter", self.filters.join(", ")));
+        }
+        entries.push(("exported", self.exported_at.clone()));
+        entries
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Object(
+            self.entries()
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), Value::String(v)))
+                .collect(),
+        )
+    }
+}
+
+/// Parsed arguments of `:export <path> [--format csv|json|yaml|md] [--raw]`
+#[derive(Debug, PartialEq)]
+pub struct ExportRequest {
+    pub path: PathBuf,
+    pub format: ExportFormat,
+    /// Write the API items instead of the rendered columns
+    pub raw: bool,
+}
+
+impl ExportRequest {
+    /// Parse the rest of the command line; options go before or after the
+    /// path, which may contain spaces
+    pub fn parse(args: &str) -> Result<Self> {
+        let mut format = None;
+        let mut raw = false;
+        // Byte range of the path words in `args`
+        let mut path_range: Option<(usize, usize)> = None;
+        let mut option_after_path = None;
+
+        let offset = |token: &str| token.as_ptr() as usize - args.as_ptr() as usize;
+        let mut tokens = args.split_whitespace();
+        while let Some(token) = tokens.next() {
+            let is_option = token.starts_with('-');
+            if is_option && path_range.is_some() {
+                option_after_path.get_or_insert(token);
+            }
+            match token {
+                "--raw" => raw = true,
+                "--format" | "-f" => match tokens.next() {
+                    Some(name) => format = Some(ExportFormat::parse(name)?),
+                    None => bail!("--format needs a value: csv, json, yaml or md"),
+                },
+                _ if token.starts_with("--format=") => {
+                    format = Some(ExportFormat::parse(&token["--format=".len()..])?)
+                }
+                _ if is_option => bail!("Unknown option {}", token),
+                _ => {
+                    if let Some(option) = option_after_path {
+                        bail!("Unexpected option {} inside the path", option);
+                    }
+                    let start = path_range.map_or(offset(token), |(start, _)| start);
+                    path_range = Some((start, offset(token) + token.len()));
+                }
+            }
+        }
+
+        let path = path_range.map(|(start, end)| expand_home(&args[start..end]));
+        let Some(path) = path else {
+            bail!("Usage: export <path> [--format csv|json|yaml|md] [--raw]");
+        };
+        let Some(format) = format.or_else(|| ExportFormat::from_path(&path)) else {
+            bail!(
+                "Cannot tell the format from {}, add --format csv|json|yaml|md",
+                path.display()
+            );
+        };
+        if raw && matches!(format, ExportFormat::Csv | ExportFormat::Markdown) {
+            bail!("--raw needs json or yaml, csv and md only hold the table columns");
+        }
+
+        Ok(ExportRequest { path, format, raw })
+    }
+}
+
+/// Render items in the given format, with their metadata except in CSV
+/// (which has no place for it)
+pub fn render_export(
+    resource: &ResourceDef,
+    items: &[Value],
+    format: ExportFormat,
+    raw: bool,
+    meta: &ExportMeta,
+) -> Result<String> {
+    match format {
+        ExportFormat::Csv => Ok(format_csv(&resource.columns, items)),
+        ExportFormat::Markdown => {
+            let header: String = meta
+                .entries()
+                .iter()
+                .map(|(k, v)| format!("- **{}**: {}\n", k, markdown_cell(v)))
+                .collect();
+            Ok(format!(
+                "## {}\n\n{}\n{}",
+                meta.resource,
+                header,
+                format_markdown(&resource.columns, items)
+            ))
+        }
+        ExportFormat::Json | ExportFormat::Yaml => {
+            let items: Vec<Value> = if raw {
+                items.to_vec()
+            } else {
+                items
+                    .iter()
+                    .map(|item| rendered_row(&resource.columns, item))
+                    .collect()
+            };
+            let mut doc = Map::new();
+            doc.insert("metadata".to_string(), meta.to_value());
+            if !raw {
+                // Object keys come out sorted, this keeps the table's column order
+                let headers = resource.columns.iter().map(|c| c.header.clone().into());
+                doc.insert("columns".to_string(), Value::Array(headers.collect()));
+            }
+            doc.insert("items".to_string(), Value::Array(items));
+            let doc = Value::Object(doc);
+
+            if format == ExportFormat::Yaml {
+                Ok(serde_yaml::to_string(&doc)?)
+            } else {
+                Ok(format!("{}\n", serde_json::to_string_pretty(&doc)?))
+            }
+        }
+    }
+}
+
+/// One item as an object of column header to cell text
+fn rendered_row(columns: &[ColumnDef], item: &Value) -> Value {
+    Value::Object(
+        columns
+            .iter()
+            .map(|c| {
+                let value = extract_json_value(item, &c.json_path);
+                let value = if value == "-" {
+                    Value::Null
+                } else {
+                    Value::String(value)
+                };
+                (c.header.clone(), value)
+            })
+            .collect(),
+    )
+}
+
+/// Aligned plain-text table with uppercase headers
+pub fn format_table(columns: &[ColumnDef], items: &[Value]) -> String {
+    let headers: Vec<String> = columns.iter().map(|c| c.header.to_uppercase()).collect();
+    let rows: Vec<Vec<String>> = items
+        .iter()
+        .map(|item| {
+            columns
+                .iter()
+                .map(|c| extract_json_value(item, &c.json_path))
+                .collect()
+        })
+        .collect();
+
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, h)| {
+            rows.iter()
+                .map(|r| r[i].chars().count())
+                .chain([h.len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut out = String::new();
+    for row in std::iter::once(&headers).chain(rows.iter()) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, w)| format!("{:w$}", cell, w = *w))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+pub fn format_csv(columns: &[ColumnDef], items: &[Value]) -> String {
+    let mut out = String::new();
+    let headers: Vec<String> = columns.iter().map(|c| csv_field(&c.header)).collect();
+    out.push_str(&headers.join(","));
+    out.push('\n');
+
+    for item in items {
+        let fields: Vec<String> = columns
+            .iter()
+            .map(|c| {
+                let value = extract_json_value(item, &c.json_path);
+                // extract_json_value renders missing fields as "-", CSV leaves them empty
+                csv_field(if value == "-" { "" } else { &value })
+            })
+            .collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// GitHub-flavored Markdown table
+pub fn format_markdown(columns: &[ColumnDef], items: &[Value]) -> String {
+    let row = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+
+    let mut out = row(columns.iter().map(|c| markdown_cell(&c.header)).collect());
+    out.push_str(&row(columns.iter().map(|_| "---".to_string()).collect()));
+    for item in items {
+        out.push_str(&row(columns
+            .iter()
+            .map(|c| markdown_cell(&extract_json_value(item, &c.json_path)))
+            .collect()));
+    }
+    out
+}
+
+/// Escape pipes and flatten newlines so a value stays in its cell
+fn markdown_cell(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::registry::get_resource;
+    use serde_json::json;
+
+    fn columns() -> Vec<ColumnDef> {
+        serde_json::from_value(json!([
+            {"header": "Name", "json_path": "name", "width": 50},
+            {"header": "Status", "json_path": "status", "width": 50}
+        ]))
+        .unwrap()
+    }
+
+    fn meta() -> ExportMeta {
+        ExportMeta {
+            resource: "VM Instances".to_string(),
+            breadcrumb: vec!["vm-instances".to_string()],
+            project: "test-project".to_string(),
+            zone: "us-central1-a".to_string(),
+            region: "us-central1".to_string(),
+            filters: vec!["status=RUNNING".to_string()],
+            exported_at: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_table() {
+        let items = vec![
+            json!({"name": "vm-1", "status": "RUNNING"}),
+            json!({"name": "long-vm-name"}),
+        ];
+        let table = format_table(&columns(), &items);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "NAME          STATUS");
+        assert_eq!(lines[1], "vm-1          RUNNING");
+        assert_eq!(lines[2], "long-vm-name  -");
+    }
+
+    #[test]
+    fn test_format_csv() {
+        let items = vec![
+            json!({"name": "a,b", "status": "say \"hi\""}),
+            json!({"name": "c"}),
+        ];
+        assert_eq!(
+            format_csv(&columns(), &items),
+            "Name,Status\n\"a,b\",\"say \"\"hi\"\"\"\nc,\n"
+        );
+    }
+
+    #[test]
+    fn test_format_markdown() {
+        let items = vec![json!({"name": "a|b", "status": "line\nbreak"})];
+        assert_eq!(
+            format_markdown(&columns(), &items),
+            "| Name | Status |\n| --- | --- |\n| a\\|b | line break |\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_export_request() {
+        let req = ExportRequest::parse("out.csv").unwrap();
+        assert_eq!(req.format, ExportFormat::Csv);
+        assert!(!req.raw);
+
+        // --format wins over the extension
+        let req = ExportRequest::parse("out.txt --format md").unwrap();
+        assert_eq!(req.format, ExportFormat::Markdown);
+
+        let req = ExportRequest::parse("--raw vms.yml").unwrap();
+        assert_eq!(req.format, ExportFormat::Yaml);
+        assert!(req.raw);
+
+        // Paths keep their spaces
+        let req = ExportRequest::parse(" -f json /tmp/my  exports/vms.out --raw").unwrap();
+        assert_eq!(req.path, PathBuf::from("/tmp/my  exports/vms.out"));
+        assert_eq!(req.format, ExportFormat::Json);
+        assert!(req.raw);
+
+        assert!(ExportRequest::parse("").is_err());
+        assert!(ExportRequest::parse("out").is_err());
+        assert!(ExportRequest::parse("out.csv --raw").is_err());
+        assert!(ExportRequest::parse("out.csv --format xls").is_err());
+        assert!(ExportRequest::parse("my --raw vms.json").is_err());
+    }
+
+    #[test]
+    fn test_render_export() {
+        let resource = get_resource("vm-instances").unwrap();
+        let items = vec![json!({"name": "vm-1", "status": "RUNNING", "id": "42"})];
+
+        // CSV has no comment syntax, the header row comes first
+        let csv = render_export(resource, &items, ExportFormat::Csv, false, &meta()).unwrap();
+        assert_eq!(csv, format_csv(&resource.columns, &items));
+
+        let md = render_export(resource, &items, ExportFormat::Markdown, false, &meta()).unwrap();
+        assert!(md.contains("- **filter**: status=RUNNING\n"));
+
+        let json = render_export(resource, &items, ExportFormat::Json, false, &meta()).unwrap();
+        let doc: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(doc["metadata"]["project"], "test-project");
+        assert_eq!(doc["items"][0]["Name"], "vm-1");
+        assert!(doc["items"][0].get("id").is_none());
+
+        let raw = render_export(resource, &items, ExportFormat::Yaml, true, &meta()).unwrap();
+        let doc: Value = serde_yaml::from_str(&raw).unwrap();
+        assert_eq!(doc["items"][0], items[0]);
+    }
+}
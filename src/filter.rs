@@ -0,0 +1,595 @@
+//! Filter expressions for the resource list
+//!
+//! A filter is either plain text, matched as a substring of the name and id
+//! fields, or an expression of predicates on any column or JSON path:
+//!
+//! ```text
+//! status=RUNNING labels.env=prod        both must match
+//! machineType~e2- | machineType~n2-     either may match
+//! !status=TERMINATED                    negation
+//! name~/^web-\d+$/                      regular expression
+//! ```
+//!
+//! Comparisons ignore case. Terms without an operator match the name and id
+//! fields like plain text does.
+
+use crate::resource::registry::{extract_json_value, ResourceDef};
+use regex::{Regex, RegexBuilder};
+use serde_json::Value;
+use std::ops::Range;
+
+/// Parsed filter text
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    expr: Option<Expr>,
+    /// Why the text could not be parsed; it is matched as plain text then
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+    Not(Box<Expr>),
+    Pred(Predicate),
+}
+
+#[derive(Debug, Clone)]
+struct Predicate {
+    /// Column header or JSON path, None for the name and id fields
+    field: Option<String>,
+    op: Op,
+    pattern: Pattern,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Equals,
+    NotEquals,
+    Contains,
+    NotContains,
+}
+
+#[derive(Debug, Clone)]
+enum Pattern {
+    /// Lowercase text
+    Text(String),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Pred(Predicate),
+}
+
+impl Filter {
+    /// Parse filter text; text without any operator keeps the plain substring behaviour
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        if text.is_empty() {
+            return Self::default();
+        }
+
+        let tokens = match lex(text) {
+            Ok(tokens) => tokens,
+            Err(e) => return Self::plain(text, Some(e)),
+        };
+        let is_plain = tokens.iter().all(|t| {
+            matches!(t, Token::Pred(p) if p.field.is_none() && matches!(p.pattern, Pattern::Text(_)))
+        });
+        if is_plain {
+            return Self::plain(text, None);
+        }
+
+        let mut parser = Parser { tokens, pos: 0 };
+        match parser.parse() {
+            Ok(expr) => Self {
+                expr: Some(expr),
+                error: None,
+            },
+            Err(e) => Self::plain(text, Some(e)),
+        }
+    }
+
+    /// Substring match of the whole text on the name and id fields
+    fn plain(text: &str, error: Option<String>) -> Self {
+        Self {
+            expr: Some(Expr::Pred(Predicate {
+                field: None,
+                op: Op::Contains,
+                pattern: Pattern::Text(text.to_lowercase()),
+            })),
+            error,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expr.is_none()
+    }
+
+    pub fn matches(&self, resource: Option<&ResourceDef>, item: &Value) -> bool {
+        self.expr
+            .as_ref()
+            .is_none_or(|expr| expr.matches(resource, item))
+    }
+
+    /// Byte ranges of `text`, the value of the column at `json_path`, matched by the filter
+    /// Only predicates that made the row match are highlighted, negated ones are skipped
+    pub fn highlights(
+        &self,
+        resource: &ResourceDef,
+        json_path: &str,
+        text: &str,
+    ) -> Vec<Range<usize>> {
+        let mut predicates = Vec::new();
+        if let Some(expr) = &self.expr {
+            expr.positive_predicates(false, &mut predicates);
+        }
+
+        let mut ranges: Vec<Range<usize>> = predicates
+            .into_iter()
+            .filter(|p| p.applies_to(resource, json_path))
+            .flat_map(|p| p.ranges(text))
+            .collect();
+        ranges.sort_by_key(|r| r.start);
+
+        // Merge overlapping ranges so each byte is highlighted once
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
+}
+
+impl Expr {
+    fn matches(&self, resource: Option<&ResourceDef>, item: &Value) -> bool {
+        match self {
+            Expr::And(exprs) => exprs.iter().all(|e| e.matches(resource, item)),
+            Expr::Or(exprs) => exprs.iter().any(|e| e.matches(resource, item)),
+            Expr::Not(expr) => !expr.matches(resource, item),
+            Expr::Pred(pred) => pred.matches(resource, item),
+        }
+    }
+
+    fn positive_predicates<'a>(&'a self, negated: bool, out: &mut Vec<&'a Predicate>) {
+        match self {
+            Expr::And(exprs) | Expr::Or(exprs) => {
+                for e in exprs {
+                    e.positive_predicates(negated, out);
+                }
+            }
+            Expr::Not(expr) => expr.positive_predicates(!negated, out),
+            Expr::Pred(pred) => {
+                if !negated && matches!(pred.op, Op::Equals | Op::Contains) {
+                    out.push(pred);
+                }
+            }
+        }
+    }
+}
+
+impl Predicate {
+    /// JSON path the field refers to, preferring column headers
+    fn path<'a>(&'a self, resource: Option<&'a ResourceDef>) -> Option<&'a str> {
+        let field = self.field.as_deref()?;
+        Some(
+            resource
+                .and_then(|r| {
+                    r.column_index(field)
+                        .map(|i| r.columns[i].json_path.as_str())
+                })
+                .unwrap_or(field),
+        )
+    }
+
+    fn matches(&self, resource: Option<&ResourceDef>, item: &Value) -> bool {
+        let values = match (self.path(resource), resource) {
+            (Some(path), _) => vec![extract_json_value(item, path)],
+            (None, Some(res)) => vec![
+                extract_json_value(item, &res.name_field),
+                extract_json_value(item, &res.id_field),
+            ],
+            // Fallback: search in JSON string
+            (None, None) => vec![item.to_string()],
+        };
+
+        let found = values.iter().any(|v| self.test(v));
+        match self.op {
+            Op::Equals | Op::Contains => found,
+            Op::NotEquals | Op::NotContains => !found,
+        }
+    }
+
+    /// Test a value against the pattern, ignoring negation
+    fn test(&self, value: &str) -> bool {
+        match &self.pattern {
+            Pattern::Regex(re) => re.is_match(value),
+            Pattern::Text(text) => match self.op {
+                Op::Equals | Op::NotEquals => value.to_lowercase() == *text,
+                Op::Contains | Op::NotContains => value.to_lowercase().contains(text.as_str()),
+            },
+        }
+    }
+
+    fn applies_to(&self, resource: &ResourceDef, json_path: &str) -> bool {
+        match self.path(Some(resource)) {
+            Some(path) => path == json_path,
+            None => json_path == resource.name_field || json_path == resource.id_field,
+        }
+    }
+
+    fn ranges(&self, text: &str) -> Vec<Range<usize>> {
+        match &self.pattern {
+            Pattern::Regex(re) => re
+                .find_iter(text)
+                .filter(|m| !m.is_empty())
+                .map(|m| m.range())
+                .collect(),
+            Pattern::Text(_) if !self.test(text) => Vec::new(),
+            Pattern::Text(_) if self.op == Op::Equals => vec![Range {
+                start: 0,
+                end: text.len(),
+            }],
+            Pattern::Text(needle) => {
+                // Byte offsets only line up when lowercasing keeps the length
+                let lower = text.to_lowercase();
+                if lower.len() != text.len() || needle.is_empty() {
+                    return Vec::new();
+                }
+                lower
+                    .match_indices(needle.as_str())
+                    .map(|(i, m)| i..i + m.len())
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Split filter text into tokens
+fn lex(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '|' | '&' => {
+                tokens.push(if c == '|' { Token::Or } else { Token::And });
+                i += 1;
+                if chars.get(i) == Some(&c) {
+                    i += 1;
+                }
+            }
+            '!' if !matches!(chars.get(i + 1), Some('=') | Some('~')) => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            _ => {
+                let (field, next) = read_atom(&chars, i, true)?;
+                i = next;
+                let op = match (chars.get(i), chars.get(i + 1)) {
+                    (Some('!'), Some('=')) => Some((Op::NotEquals, 2)),
+                    (Some('!'), Some('~')) => Some((Op::NotContains, 2)),
+                    (Some('='), _) => Some((Op::Equals, 1)),
+                    (Some('~'), _) => Some((Op::Contains, 1)),
+                    _ => None,
+                };
+
+                let token = match op {
+                    Some((op, len)) => {
+                        let Atom::Text(field) = field else {
+                            return Err("a regex cannot be used as a field name".to_string());
+                        };
+                        if field.is_empty() {
+                            return Err("missing field name before operator".to_string());
+                        }
+                        let (value, next) = read_atom(&chars, i + len, false)?;
+                        i = next;
+                        Token::Pred(Predicate {
+                            field: Some(field),
+                            op,
+                            pattern: value.into_pattern()?,
+                        })
+                    }
+                    None => match field {
+                        Atom::Text(word) if word == "AND" => Token::And,
+                        Atom::Text(word) if word == "OR" => Token::Or,
+                        Atom::Text(word) if word == "NOT" => Token::Not,
+                        atom => Token::Pred(Predicate {
+                            field: None,
+                            op: Op::Contains,
+                            pattern: atom.into_pattern()?,
+                        }),
+                    },
+                };
+                tokens.push(token);
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Field name, value or word read by the lexer
+enum Atom {
+    Text(String),
+    Regex(String),
+}
+
+impl Atom {
+    fn into_pattern(self) -> Result<Pattern, String> {
+        match self {
+            Atom::Text(text) => Ok(Pattern::Text(text.to_lowercase())),
+            Atom::Regex(re) => RegexBuilder::new(&re)
+                .case_insensitive(true)
+                .build()
+                .map(Pattern::Regex)
+                .map_err(|e| format!("invalid regex /{}/: {}", re, e)),
+        }
+    }
+}
+
+/// Read a quoted string, a /regex/ or a bare run of characters starting at `start`
+/// Bare field names also stop at operator characters
+fn read_atom(chars: &[char], start: usize, is_field: bool) -> Result<(Atom, usize), String> {
+    let mut i = start;
+    match chars.get(i) {
+        Some(&quote) if quote == '"' || quote == '\'' => {
+            let mut text = String::new();
+            i += 1;
+            while let Some(&c) = chars.get(i) {
+                if c == quote {
+                    return Ok((Atom::Text(text), i + 1));
+                }
+                text.push(c);
+                i += 1;
+            }
+            Err(format!("unterminated {} quote", quote))
+        }
+        Some('/') => {
+            let mut re = String::new();
+            i += 1;
+            while let Some(&c) = chars.get(i) {
+                match c {
+                    '/' => return Ok((Atom::Regex(re), i + 1)),
+                    // An escaped slash is part of the regex
+                    '\\' if chars.get(i + 1) == Some(&'/') => {
+                        re.push('/');
+                        i += 2;
+                    }
+                    _ => {
+                        re.push(c);
+                        i += 1;
+                    }
+                }
+            }
+            Err("unterminated regex, expected closing /".to_string())
+        }
+        _ => {
+            let mut text = String::new();
+            while let Some(&c) = chars.get(i) {
+                let stop = c.is_whitespace()
+                    || c == '('
+                    || c == ')'
+                    || c == '|'
+                    || c == '&'
+                    || (is_field && (c == '=' || c == '~' || c == '!'));
+                if stop {
+                    break;
+                }
+                text.push(c);
+                i += 1;
+            }
+            Ok((Atom::Text(text), i))
+        }
+    }
+}
+
+/// Recursive descent parser, AND binds tighter than OR and is implied between terms
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn parse(&mut self) -> Result<Expr, String> {
+        let expr = self.or()?;
+        match self.tokens.get(self.pos) {
+            None => Ok(expr),
+            Some(Token::RParen) => Err("unexpected )".to_string()),
+            Some(_) => Err("unexpected operator".to_string()),
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut exprs = vec![self.and()?];
+        while matches!(self.tokens.get(self.pos), Some(Token::Or)) {
+            self.pos += 1;
+            exprs.push(self.and()?);
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            Expr::Or(exprs)
+        })
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut exprs = vec![self.unary()?];
+        loop {
+            match self.tokens.get(self.pos) {
+                Some(Token::And) => {
+                    self.pos += 1;
+                    exprs.push(self.unary()?);
+                }
+                Some(Token::Not) | Some(Token::LParen) | Some(Token::Pred(_)) => {
+                    exprs.push(self.unary()?);
+                }
+                _ => break,
+            }
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            Expr::And(exprs)
+        })
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        match token {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::LParen) => {
+                let expr = self.or()?;
+                match self.tokens.get(self.pos) {
+                    Some(Token::RParen) => {
+                        self.pos += 1;
+                        Ok(expr)
+                    }
+                    _ => Err("missing )".to_string()),
+                }
+            }
+            Some(Token::Pred(pred)) => Ok(Expr::Pred(pred)),
+            Some(_) => Err("unexpected operator".to_string()),
+            None => Err("incomplete expression".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::registry::get_resource;
+    use serde_json::json;
+
+    fn vm(name: &str, status: &str, machine: &str, env: &str) -> Value {
+        json!({
+            "name": name,
+            "id": "123",
+            "status": status,
+            "machineType": format!("https://www.googleapis.com/compute/v1/projects/p/zones/z/machineTypes/{}", machine),
+            "labels": {"env": env},
+        })
+    }
+
+    fn matching(filter: &str) -> Vec<String> {
+        let resource = get_resource("vm-instances");
+        let items = [
+            vm("web-1", "RUNNING", "e2-medium", "prod"),
+            vm("web-2", "TERMINATED", "n2-standard-4", "prod"),
+            vm("db-1", "RUNNING", "n2-standard-8", "dev"),
+        ];
+        let filter = Filter::parse(filter);
+        assert!(filter.error.is_none(), "{:?}", filter.error);
+        items
+            .iter()
+            .filter(|i| filter.matches(resource, i))
+            .map(|i| i["name"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(matching("WEB"), vec!["web-1", "web-2"]);
+        assert_eq!(matching("123"), vec!["web-1", "web-2", "db-1"]);
+        // Words without operators keep matching the whole text
+        assert!(matching("web 1").is_empty());
+    }
+
+    #[test]
+    fn test_predicates() {
+        assert_eq!(matching("status=running"), vec!["web-1", "db-1"]);
+        assert_eq!(matching("labels.env=prod"), vec!["web-1", "web-2"]);
+        assert_eq!(matching("machineType~e2-"), vec!["web-1"]);
+        // Column headers work as field names
+        assert_eq!(matching("'Machine Type'~n2"), vec!["web-2", "db-1"]);
+        assert_eq!(matching("status!=RUNNING"), vec!["web-2"]);
+        assert_eq!(matching("name!~web"), vec!["db-1"]);
+    }
+
+    #[test]
+    fn test_boolean_operators() {
+        assert_eq!(matching("status=RUNNING labels.env=prod"), vec!["web-1"]);
+        assert_eq!(matching("status=RUNNING AND !web"), vec!["db-1"]);
+        assert_eq!(
+            matching("labels.env=dev | status=TERMINATED"),
+            vec!["web-2", "db-1"]
+        );
+        assert_eq!(
+            matching("web (status=TERMINATED OR machineType~e2)"),
+            vec!["web-1", "web-2"]
+        );
+        assert_eq!(
+            matching("NOT (labels.env=prod && status=RUNNING)"),
+            vec!["web-2", "db-1"]
+        );
+    }
+
+    #[test]
+    fn test_regex() {
+        assert_eq!(matching("/^web-\\d$/"), vec!["web-1", "web-2"]);
+        assert_eq!(
+            matching("machineType~/standard-[48]$/"),
+            vec!["web-2", "db-1"]
+        );
+        assert_eq!(matching("name~/^DB|-2$/"), vec!["web-2", "db-1"]);
+    }
+
+    #[test]
+    fn test_invalid_filter_falls_back_to_text() {
+        let resource = get_resource("vm-instances");
+        let item = vm("a(b", "RUNNING", "e2-medium", "prod");
+
+        let filter = Filter::parse("a(b");
+        assert!(filter.error.is_some());
+        assert!(filter.matches(resource, &item));
+
+        let filter = Filter::parse("name~/[/");
+        assert!(filter.error.unwrap().contains("invalid regex"));
+    }
+
+    #[test]
+    fn test_highlights() {
+        let resource = get_resource("vm-instances").unwrap();
+        let status_path = &resource.columns[resource.column_index("status").unwrap()].json_path;
+
+        let filter = Filter::parse("web status=running");
+        assert_eq!(
+            filter.highlights(resource, &resource.name_field, "my-Web-web"),
+            vec![3..6, 7..10]
+        );
+        assert_eq!(
+            filter.highlights(resource, status_path, "RUNNING"),
+            vec![Range { start: 0, end: 7 }]
+        );
+
+        // Negated predicates are not highlighted
+        let filter = Filter::parse("!web status!=STOPPED");
+        assert!(filter
+            .highlights(resource, &resource.name_field, "web-1")
+            .is_empty());
+        assert!(filter
+            .highlights(resource, status_path, "RUNNING")
+            .is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+//! Fuzzy matching for pickers
+//!
+//! A query matches when its characters appear in order in the candidate
+//! (e.g., `euw1b` matches `europe-west1-b`). Matches at word starts and
+//! consecutive characters rank higher.
+
+/// Score of a candidate for a query, None if it does not match
+pub fn score(query: &str, candidate: &str) -> Option<i64> {
+    let mut query_chars = query.chars().flat_map(char::to_lowercase).peekable();
+    let mut score = 0;
+    let mut prev: Option<char> = None;
+    let mut prev_matched = false;
+
+    for c in candidate.chars().flat_map(char::to_lowercase) {
+        let Some(&q) = query_chars.peek() else {
+            break;
+        };
+        if c == q {
+            score += 1;
+            if prev_matched {
+                score += 4;
+            }
+            if prev.is_none_or(|p| !p.is_alphanumeric()) {
+                score += 6;
+            }
+            query_chars.next();
+            prev_matched = true;
+        } else {
+            prev_matched = false;
+        }
+        prev = Some(c);
+    }
+
+    if query_chars.peek().is_some() {
+        return None;
+    }
+    // Prefer shorter candidates among equal matches
+    Some(score * 100 - candidate.len() as i64)
+}
+
+/// Items matching the query, best first; an empty query keeps every item in order
+pub fn filter<'a, T>(query: &str, items: &'a [T], key: impl Fn(&T) -> &str) -> Vec<&'a T> {
+    let query = query.trim();
+    if query.is_empty() {
+        return items.iter().collect();
+    }
+
+    let mut scored: Vec<(i64, &T)> = items
+        .iter()
+        .filter_map(|item| score(query, key(item)).map(|s| (s, item)))
+        .collect();
+    // Stable sort keeps the original order among equal scores
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        assert!(score("euw1b", "europe-west1-b").is_some());
+        assert!(score("EUW", "europe-west1-b").is_some());
+        assert!(score("wue", "europe-west1-b").is_none());
+        assert!(score("", "anything").is_some());
+
+        // Word starts and runs beat scattered matches
+        assert!(score("ew", "europe-west1") > score("ew", "asia-east1-new"));
+        assert!(score("ea", "us-east1-b") > score("ea", "us-central1-a"));
+    }
+
+    #[test]
+    fn test_filter() {
+        let zones = [
+            "us-central1-a",
+            "us-east1-b",
+            "europe-west1-b",
+            "asia-east1-a",
+        ];
+
+        assert_eq!(filter("", &zones, |z| z).len(), 4);
+        assert_eq!(
+            filter("east", &zones, |z| z),
+            vec![&"us-east1-b", &"asia-east1-a"]
+        );
+        assert_eq!(filter("euw", &zones, |z| z), vec![&"europe-west1-b"]);
+        assert!(filter("xyz", &zones, |z| z).is_empty());
+    }
+}
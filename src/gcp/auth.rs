@@ -3,27 +3,168 @@ use chrono::{Duration, Utc};
 use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs;
 use std::path::PathBuf;
-use std::sync::RwLock;
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
 use tracing::{debug, info, trace, warn};
 
+use super::gcloud;
+
 const TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
 const METADATA_TOKEN_URL: &str =
     "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+const METADATA_EMAIL_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/email";
 const METADATA_PROJECT_URL: &str =
     "http://metadata.google.internal/computeMetadata/v1/project/project-id";
+const STS_TOKEN_URL: &str = "https://sts.googleapis.com/v1/token";
+const IAM_CREDENTIALS_URL: &str = "https://iamcredentials.googleapis.com/v1";
+const TOKEN_INFO_URL: &str = "https://oauth2.googleapis.com/tokeninfo";
+const CLOUD_PLATFORM_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
+/// Tokens are refreshed this long before they expire
+const REFRESH_MARGIN: Duration = Duration::minutes(5);
 
-/// Cached token with expiry
+/// Access token with expiry and the identity it acts as
 #[derive(Debug, Clone)]
-struct CachedToken {
+struct AccessToken {
     token: String,
     expires_at: chrono::DateTime<Utc>,
+    /// Email of the account, if known
+    identity: Option<String>,
+    source: AuthSource,
+    /// Granted scopes, empty if unknown
+    scopes: Vec<String>,
+}
+
+/// Kind of credential a token was obtained with
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthSource {
+    /// GCP_ACCESS_TOKEN
+    EnvToken,
+    /// Service account key
+    KeyFile,
+    /// User refresh token from `gcloud auth application-default login`
+    AdcUser,
+    /// Metadata server of the GCP environment
+    Metadata,
+    /// Federated (external_account) credentials
+    WorkloadIdentity,
+    /// Service account token created with another credential
+    Impersonation(Box<AuthSource>),
+}
+
+impl fmt::Display for AuthSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EnvToken => write!(f, "env token"),
+            Self::KeyFile => write!(f, "key file"),
+            Self::AdcUser => write!(f, "ADC user"),
+            Self::Metadata => write!(f, "metadata"),
+            Self::WorkloadIdentity => write!(f, "workload identity"),
+            Self::Impersonation(base) => write!(f, "impersonation via {}", base),
+        }
+    }
+}
+
+/// Who the current credentials act as, for the header and :whoami
+#[derive(Debug, Clone)]
+pub struct TokenIdentity {
+    pub source: AuthSource,
+    /// Account email, if known
+    pub principal: Option<String>,
+    /// Granted scopes, empty if unknown
+    pub scopes: Vec<String>,
+    pub expires_at: chrono::DateTime<Utc>,
+}
+
+impl AccessToken {
+    /// True until the token is within the refresh margin of expiring
+    fn is_fresh(&self) -> bool {
+        self.expires_at - REFRESH_MARGIN > Utc::now()
+    }
+
+    /// True until the token expires
+    fn is_valid(&self) -> bool {
+        self.expires_at > Utc::now()
+    }
+}
+
+/// Identifies the tokens of one credential: where the base credential
+/// comes from and who is impersonated through which delegates. Every token
+/// is requested with CLOUD_PLATFORM_SCOPE, so the scope isn't part of it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TokenKey {
+    source: CredentialSource,
+    subject: Option<String>,
+    delegates: Vec<String>,
+}
+
+impl TokenKey {
+    fn new(config: &CredentialConfig) -> Self {
+        Self {
+            source: config.source.clone(),
+            subject: config.impersonate_service_account.clone(),
+            delegates: config.delegates.clone(),
+        }
+    }
+}
+
+/// Tokens of every credential used this session
+static TOKEN_CACHE: LazyLock<RwLock<HashMap<TokenKey, AccessToken>>> =
+    LazyLock::new(Default::default);
+
+/// One lock per credential, so concurrent refreshes make a single request
+static REFRESH_LOCKS: LazyLock<Mutex<HashMap<TokenKey, Arc<tokio::sync::Mutex<()>>>>> =
+    LazyLock::new(Default::default);
+
+/// Credentials selected by the active context
+static CREDENTIALS: RwLock<CredentialConfig> = RwLock::new(CredentialConfig {
+    source: CredentialSource::Default,
+    impersonate_service_account: None,
+    delegates: Vec::new(),
+});
+
+/// Where the base credential comes from
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum CredentialSource {
+    /// Environment, ADC file, then metadata server
+    #[default]
+    Default,
+    /// Application Default Credentials file only, ignoring credential env vars
+    Adc,
+    /// Service account key or other credential file
+    File(PathBuf),
 }
 
-/// Global token cache
-static TOKEN_CACHE: RwLock<Option<CachedToken>> = RwLock::new(None);
+/// Credentials used for API calls
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CredentialConfig {
+    pub source: CredentialSource,
+    /// Service account whose short-lived tokens are used instead of the base credential
+    pub impersonate_service_account: Option<String>,
+    /// Service accounts between the base credential and the impersonated one,
+    /// each allowed to create tokens for the next
+    pub delegates: Vec<String>,
+}
+
+impl CredentialConfig {
+    /// Impersonate a service account or a comma-separated delegation chain
+    /// ending with the target, as gcloud's `--impersonate-service-account`
+    pub fn impersonating(mut self, chain: &str) -> Self {
+        let mut accounts: Vec<String> = chain
+            .split(',')
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty())
+            .collect();
+        self.impersonate_service_account = accounts.pop();
+        self.delegates = accounts;
+        self
+    }
+}
 
 /// Service account credentials from JSON file
 #[derive(Debug, Deserialize)]
@@ -68,29 +209,245 @@ struct TokenResponse {
     token_type: Option<String>,
 }
 
+impl TokenResponse {
+    fn into_access_token(self, source: AuthSource) -> AccessToken {
+        AccessToken {
+            token: self.access_token,
+            expires_at: Utc::now() + Duration::seconds(self.expires_in.unwrap_or(3600)),
+            identity: None,
+            source,
+            scopes: Vec::new(),
+        }
+    }
+}
+
+/// Response of the OAuth2 tokeninfo endpoint (numbers are strings)
+#[derive(Debug, Deserialize)]
+struct TokenInfo {
+    email: Option<String>,
+    expires_in: Option<String>,
+    /// Space-separated granted scopes
+    scope: Option<String>,
+}
+
+impl TokenInfo {
+    fn scopes(&self) -> Vec<String> {
+        self.scope
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(|s| s.to_string())
+            .collect()
+    }
+}
+
+/// ADC file written by `gcloud auth application-default login --impersonate-service-account`
+#[derive(Debug, Deserialize)]
+struct ImpersonatedCredentials {
+    service_account_impersonation_url: String,
+    #[serde(default)]
+    delegates: Vec<String>,
+    source_credentials: serde_json::Value,
+}
+
+/// Workload identity federation config (`gcloud iam workload-identity-pools create-cred-config`)
+#[derive(Debug, Deserialize)]
+struct ExternalAccountCredentials {
+    /// Workload identity pool provider, e.g. "//iam.googleapis.com/projects/.../providers/..."
+    audience: String,
+    subject_token_type: String,
+    token_url: Option<String>,
+    service_account_impersonation_url: Option<String>,
+    /// Project billed for workforce pool calls
+    workforce_pool_user_project: Option<String>,
+    credential_source: ExternalCredentialSource,
+}
+
+/// Where an external account reads the subject token exchanged at STS
+#[derive(Debug, Deserialize)]
+struct ExternalCredentialSource {
+    file: Option<String>,
+    url: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    format: Option<SubjectTokenFormat>,
+    /// Set for AWS sources, e.g. "aws1"
+    environment_id: Option<String>,
+    executable: Option<serde_json::Value>,
+}
+
+/// Format of a file- or URL-sourced subject token
+#[derive(Debug, Deserialize)]
+struct SubjectTokenFormat {
+    /// "text" or "json"
+    #[serde(rename = "type", default)]
+    format_type: Option<String>,
+    /// Field holding the token in a JSON response
+    subject_token_field_name: Option<String>,
+}
+
+/// Response of IAM Credentials `generateAccessToken`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeneratedToken {
+    access_token: String,
+    expire_time: chrono::DateTime<Utc>,
+}
+
 #[derive(Debug, Clone)]
 pub struct TokenProvider;
 
 impl TokenProvider {
-    /// Get an access token using the following priority:
-    /// 1. GCP_ACCESS_TOKEN env var (direct token)
-    /// 2. GOOGLE_CREDENTIALS env var (inline JSON)
-    /// 3. GOOGLE_APPLICATION_CREDENTIALS env var (path to JSON file)
-    /// 4. Application Default Credentials (~/.config/gcloud/application_default_credentials.json)
-    /// 5. GCP metadata server (for running on GCP infrastructure)
+    /// Use other credentials for later calls, tokens of the previous ones stay cached
+    pub fn configure(config: CredentialConfig) {
+        if let Ok(mut current) = CREDENTIALS.write() {
+            if *current != config {
+                info!("Switching credentials: {:?}", config);
+                *current = config;
+            }
+        }
+    }
+
+    /// Credentials in use
+    pub fn credential_config() -> CredentialConfig {
+        CREDENTIALS.read().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    /// Who the current credentials act as, once a token was fetched
+    pub fn identity() -> Option<TokenIdentity> {
+        let key = TokenKey::new(&Self::credential_config());
+        let cache = TOKEN_CACHE.read().ok()?;
+        let token = cache.get(&key)?;
+        Some(TokenIdentity {
+            source: token.source.clone(),
+            principal: token.identity.clone(),
+            scopes: token.scopes.clone(),
+            expires_at: token.expires_at,
+        })
+    }
+
+    /// Get an access token for the configured credentials, refreshed shortly
+    /// before it expires; concurrent callers share a single refresh
     pub async fn get_token() -> Result<String> {
         debug!("Getting access token");
+        let config = Self::credential_config();
+        let key = TokenKey::new(&config);
 
-        // Check cache first
-        if let Some(token) = Self::get_cached_token() {
+        if let Some(token) = Self::cached_token(&key).filter(|t| t.is_fresh()) {
             trace!("Using cached token");
-            return Ok(token);
+            return Ok(token.token);
+        }
+
+        let lock = Self::refresh_lock(&key);
+        let _guard = lock.lock().await;
+
+        // Another caller may have refreshed while we waited
+        let cached = Self::cached_token(&key);
+        if let Some(token) = cached.as_ref().filter(|t| t.is_fresh()) {
+            trace!("Using token refreshed by another task");
+            return Ok(token.token.clone());
+        }
+
+        match Self::fetch_token(&config).await {
+            Ok(token) => {
+                if let Ok(mut cache) = TOKEN_CACHE.write() {
+                    cache.insert(key, token.clone());
+                }
+                Ok(token.token)
+            }
+            // A failed early refresh keeps using the token until it expires
+            Err(e) => match cached.filter(|t| t.is_valid()) {
+                Some(token) => {
+                    warn!("Token refresh failed, using current token: {}", e);
+                    Ok(token.token)
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Get a new token for the credentials, impersonating the configured
+    /// service account if any
+    async fn fetch_token(config: &CredentialConfig) -> Result<AccessToken> {
+        let base = match &config.source {
+            CredentialSource::Default => Self::get_default_token().await?,
+            CredentialSource::Adc => Self::get_adc_token()
+                .await?
+                .ok_or_else(|| anyhow!("No Application Default Credentials file found"))?,
+            CredentialSource::File(path) => {
+                info!("Using credentials from {:?}", path);
+                let json = fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read credentials file: {:?}", path))?;
+                Self::get_token_from_json(&json).await?
+            }
+        };
+
+        let mut token = match &config.impersonate_service_account {
+            Some(account) => {
+                let url = generate_access_token_url(account);
+                Self::impersonate(&base, &url, &config.delegates).await?
+            }
+            None => base,
+        };
+        if token.identity.is_none() || token.scopes.is_empty() {
+            // User, metadata and federated tokens only tell who they are through tokeninfo
+            match Self::token_info(&token.token).await {
+                Ok(info) => {
+                    if token.identity.is_none() {
+                        token.identity = info.email.clone();
+                    }
+                    if token.scopes.is_empty() {
+                        token.scopes = info.scopes();
+                    }
+                }
+                Err(e) => debug!("No token info: {}", e),
+            }
         }
+        Ok(token)
+    }
 
+    /// Look up the account and remaining lifetime of a token
+    async fn token_info(token: &str) -> Result<TokenInfo> {
+        let resp = Client::builder()
+            .timeout(std::time::Duration::from_secs(5))
+            .build()?
+            .get(TOKEN_INFO_URL)
+            .query(&[("access_token", token)])
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(anyhow!("Token info returned {}", resp.status()));
+        }
+        Ok(resp.json().await?)
+    }
+
+    /// Get an access token using the following priority:
+    /// 1. GCP_ACCESS_TOKEN env var (direct token)
+    /// 2. GOOGLE_CREDENTIALS env var (inline JSON)
+    /// 3. GOOGLE_APPLICATION_CREDENTIALS env var (path to JSON file)
+    /// 4. Application Default Credentials (~/.config/gcloud/application_default_credentials.json)
+    /// 5. GCP metadata server (for running on GCP infrastructure)
+    async fn get_default_token() -> Result<AccessToken> {
         // 1. Direct token from env
         if let Ok(token) = env::var("GCP_ACCESS_TOKEN") {
             info!("Using token from GCP_ACCESS_TOKEN env var");
-            return Ok(token);
+            // Expiry comes from tokeninfo; if unknown, read the variable again after a while
+            let info = Self::token_info(&token)
+                .await
+                .map_err(|e| warn!("Failed to look up GCP_ACCESS_TOKEN: {}", e))
+                .ok();
+            let lifetime = info
+                .as_ref()
+                .and_then(|i| i.expires_in.as_deref()?.parse::<i64>().ok())
+                .map(Duration::seconds)
+                .unwrap_or(REFRESH_MARGIN + Duration::minutes(5));
+            return Ok(AccessToken {
+                token,
+                expires_at: Utc::now() + lifetime,
+                scopes: info.as_ref().map(|i| i.scopes()).unwrap_or_default(),
+                identity: info.and_then(|i| i.email),
+                source: AuthSource::EnvToken,
+            });
         }
 
         // 2. Inline JSON credentials
@@ -111,14 +468,8 @@ impl TokenProvider {
         }
 
         // 4. Application Default Credentials (multiple possible locations)
-        for adc_path in Self::get_adc_paths() {
-            debug!("Checking ADC path: {:?}", adc_path);
-            if adc_path.exists() {
-                info!("Using Application Default Credentials from: {:?}", adc_path);
-                let json = fs::read_to_string(&adc_path)
-                    .with_context(|| format!("Failed to read ADC file: {:?}", adc_path))?;
-                return Self::get_token_from_json(&json).await;
-            }
+        if let Some(token) = Self::get_adc_token().await? {
+            return Ok(token);
         }
 
         // 5. Metadata server (GCP environment)
@@ -137,7 +488,69 @@ impl TokenProvider {
         ))
     }
 
-    /// Get project ID from credentials or environment
+    /// Get a token from the first Application Default Credentials file found
+    async fn get_adc_token() -> Result<Option<AccessToken>> {
+        for adc_path in Self::get_adc_paths() {
+            debug!("Checking ADC path: {:?}", adc_path);
+            if adc_path.exists() {
+                info!("Using Application Default Credentials from: {:?}", adc_path);
+                let json = fs::read_to_string(&adc_path)
+                    .with_context(|| format!("Failed to read ADC file: {:?}", adc_path))?;
+                return Self::get_token_from_json(&json).await.map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Exchange a token for a short-lived token of a service account through
+    /// its `generateAccessToken` URL (needs `roles/iam.serviceAccountTokenCreator`
+    /// on the account, or on each delegate for the one after it)
+    async fn impersonate(
+        base: &AccessToken,
+        url: &str,
+        delegates: &[String],
+    ) -> Result<AccessToken> {
+        let account = impersonated_account(url);
+        info!(
+            "Impersonating service account {} (delegates: {:?})",
+            account, delegates
+        );
+
+        let resp = Client::new()
+            .post(url)
+            .bearer_auth(&base.token)
+            .json(&generate_access_token_body(delegates))
+            .send()
+            .await
+            .context("Failed to request impersonated token")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Impersonating {} failed ({}): {}",
+                account,
+                status,
+                text
+            ));
+        }
+
+        let generated: GeneratedToken = resp
+            .json()
+            .await
+            .context("Failed to parse impersonated token")?;
+        Ok(AccessToken {
+            token: generated.access_token,
+            expires_at: generated.expire_time,
+            identity: Some(account.to_string()),
+            source: AuthSource::Impersonation(Box::new(base.source.clone())),
+            scopes: vec![CLOUD_PLATFORM_SCOPE.to_string()],
+        })
+    }
+
+    /// Get project ID from the environment, gcloud or the credentials, in the
+    /// order of `Config::effective_project`, which also checks the config file
+    /// between gcloud and the credentials
     pub async fn get_project() -> Result<String> {
         debug!("Getting GCP project");
 
@@ -155,7 +568,13 @@ impl TokenProvider {
             return Ok(p);
         }
 
-        // 2. From credentials file
+        // 2. Active gcloud configuration (core/project)
+        if let Some(project) = gcloud::active().and_then(|c| c.project) {
+            info!("Using project from gcloud configuration: {}", project);
+            return Ok(project);
+        }
+
+        // 3. From credentials file
         if let Ok(json) = env::var("GOOGLE_CREDENTIALS") {
             if let Ok(creds) = serde_json::from_str::<ServiceAccountCredentials>(&json) {
                 if let Some(project) = creds.project_id {
@@ -179,7 +598,7 @@ impl TokenProvider {
             }
         }
 
-        // 3. From ADC files
+        // 4. From ADC files
         for adc_path in Self::get_adc_paths() {
             debug!("Checking ADC for project: {:?}", adc_path);
             if let Ok(json) = fs::read_to_string(&adc_path) {
@@ -199,7 +618,7 @@ impl TokenProvider {
             }
         }
 
-        // 4. From metadata server
+        // 5. From metadata server
         debug!("Trying to get project from metadata server");
         if let Ok(project) = Self::get_project_from_metadata().await {
             info!("Using project from metadata server: {}", project);
@@ -210,12 +629,13 @@ impl TokenProvider {
         Err(anyhow!(
             "No GCP project found. Please either:\n\
              - Set GCP_PROJECT or GOOGLE_CLOUD_PROJECT environment variable\n\
+             - Run 'gcloud config set project YOUR_PROJECT'\n\
              - Run 'gcloud auth application-default login --project YOUR_PROJECT'"
         ))
     }
 
-    /// Get token from JSON credentials (service account or user)
-    async fn get_token_from_json(json: &str) -> Result<String> {
+    /// Get token from JSON credentials (service account, user, impersonated or external account)
+    async fn get_token_from_json(json: &str) -> Result<AccessToken> {
         let value: serde_json::Value =
             serde_json::from_str(json).context("Failed to parse credentials JSON")?;
 
@@ -235,12 +655,32 @@ impl TokenProvider {
                     serde_json::from_str(json).context("Failed to parse user credentials JSON")?;
                 Self::get_token_from_user_credentials(creds).await
             }
+            "external_account" => {
+                let creds: ExternalAccountCredentials =
+                    serde_json::from_str(json).context("Failed to parse external account JSON")?;
+                Self::get_token_from_external_account(creds).await
+            }
+            "impersonated_service_account" => {
+                let creds: ImpersonatedCredentials = serde_json::from_str(json)
+                    .context("Failed to parse impersonated credentials JSON")?;
+                // Source credentials are never impersonated themselves, boxed for the recursion
+                let source = creds.source_credentials.to_string();
+                let base = Box::pin(Self::get_token_from_json(&source)).await?;
+                Self::impersonate(
+                    &base,
+                    &creds.service_account_impersonation_url,
+                    &creds.delegates,
+                )
+                .await
+            }
             _ => Err(anyhow!("Unknown credential type: {}", cred_type)),
         }
     }
 
     /// Get token using service account credentials (JWT -> OAuth2)
-    async fn get_token_from_service_account(creds: ServiceAccountCredentials) -> Result<String> {
+    async fn get_token_from_service_account(
+        creds: ServiceAccountCredentials,
+    ) -> Result<AccessToken> {
         let client_email = creds
             .client_email
             .ok_or_else(|| anyhow!("Missing client_email in service account"))?;
@@ -258,7 +698,7 @@ impl TokenProvider {
             aud: token_uri.clone(),
             iat: now.timestamp(),
             exp: exp.timestamp(),
-            scope: "https://www.googleapis.com/auth/cloud-platform".to_string(),
+            scope: CLOUD_PLATFORM_SCOPE.to_string(),
         };
 
         let header = Header::new(Algorithm::RS256);
@@ -266,6 +706,7 @@ impl TokenProvider {
             .context("Failed to parse private key")?;
 
         let jwt = encode(&header, &claims, &key).context("Failed to encode JWT")?;
+        let identity = claims.sub;
 
         // Exchange JWT for access token
         let client = Client::new();
@@ -292,15 +733,15 @@ impl TokenProvider {
             .await
             .context("Failed to parse token response")?;
 
-        // Cache the token
-        let expires_at = Utc::now() + Duration::seconds(token_resp.expires_in.unwrap_or(3600) - 60);
-        Self::cache_token(&token_resp.access_token, expires_at);
-
-        Ok(token_resp.access_token)
+        Ok(AccessToken {
+            identity: Some(identity),
+            scopes: vec![CLOUD_PLATFORM_SCOPE.to_string()],
+            ..token_resp.into_access_token(AuthSource::KeyFile)
+        })
     }
 
     /// Get token using user credentials (refresh token flow)
-    async fn get_token_from_user_credentials(creds: UserCredentials) -> Result<String> {
+    async fn get_token_from_user_credentials(creds: UserCredentials) -> Result<AccessToken> {
         let client_id = creds
             .client_id
             .ok_or_else(|| anyhow!("Missing client_id in user credentials"))?;
@@ -337,15 +778,77 @@ impl TokenProvider {
             .await
             .context("Failed to parse token response")?;
 
-        // Cache the token
-        let expires_at = Utc::now() + Duration::seconds(token_resp.expires_in.unwrap_or(3600) - 60);
-        Self::cache_token(&token_resp.access_token, expires_at);
+        Ok(token_resp.into_access_token(AuthSource::AdcUser))
+    }
+
+    /// Get token for a workload identity federation config: read the subject
+    /// token, exchange it at STS, then impersonate the service account if set
+    async fn get_token_from_external_account(
+        creds: ExternalAccountCredentials,
+    ) -> Result<AccessToken> {
+        info!("Using external account for {}", creds.audience);
+        let subject_token = Self::get_subject_token(&creds.credential_source).await?;
+        let token_url = creds.token_url.as_deref().unwrap_or(STS_TOKEN_URL);
+
+        let resp = Client::new()
+            .post(token_url)
+            .form(&sts_exchange_params(&creds, &subject_token))
+            .send()
+            .await
+            .context("Failed to request STS token exchange")?;
 
-        Ok(token_resp.access_token)
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            return Err(anyhow!("STS token exchange failed ({}): {}", status, text));
+        }
+
+        let federated = resp
+            .json::<TokenResponse>()
+            .await
+            .context("Failed to parse STS token response")?
+            .into_access_token(AuthSource::WorkloadIdentity);
+
+        match &creds.service_account_impersonation_url {
+            Some(url) => Self::impersonate(&federated, url, &[]).await,
+            None => Ok(federated),
+        }
+    }
+
+    /// Read the subject token of an external account from its file or URL
+    async fn get_subject_token(source: &ExternalCredentialSource) -> Result<String> {
+        let raw = if let Some(path) = &source.file {
+            debug!("Reading subject token from {}", path);
+            fs::read_to_string(path)
+                .with_context(|| format!("Failed to read subject token file: {}", path))?
+        } else if let Some(url) = &source.url {
+            debug!("Fetching subject token from {}", url);
+            let mut request = Client::new().get(url);
+            for (name, value) in &source.headers {
+                request = request.header(name, value);
+            }
+            let resp = request
+                .send()
+                .await
+                .context("Failed to fetch subject token")?;
+            if !resp.status().is_success() {
+                return Err(anyhow!("Fetching subject token failed ({})", resp.status()));
+            }
+            resp.text().await.context("Failed to read subject token")?
+        } else if source.environment_id.is_some() || source.executable.is_some() {
+            return Err(anyhow!(
+                "Only file- and URL-sourced external account credentials are supported"
+            ));
+        } else {
+            return Err(anyhow!(
+                "External account has no credential_source file or url"
+            ));
+        };
+        parse_subject_token(&raw, source.format.as_ref())
     }
 
     /// Get token from GCP metadata server (for VMs, Cloud Run, GKE)
-    async fn get_token_from_metadata() -> Result<String> {
+    async fn get_token_from_metadata() -> Result<AccessToken> {
         let client = Client::builder()
             .timeout(std::time::Duration::from_secs(2))
             .build()?;
@@ -362,11 +865,21 @@ impl TokenProvider {
 
         let token_resp: TokenResponse = resp.json().await?;
 
-        // Cache the token
-        let expires_at = Utc::now() + Duration::seconds(token_resp.expires_in.unwrap_or(3600) - 60);
-        Self::cache_token(&token_resp.access_token, expires_at);
+        // Default service account of the instance, best effort
+        let identity = match client
+            .get(METADATA_EMAIL_URL)
+            .header("Metadata-Flavor", "Google")
+            .send()
+            .await
+        {
+            Ok(resp) if resp.status().is_success() => resp.text().await.ok(),
+            _ => None,
+        };
 
-        Ok(token_resp.access_token)
+        Ok(AccessToken {
+            identity: identity.map(|email| email.trim().to_string()),
+            ..token_resp.into_access_token(AuthSource::Metadata)
+        })
     }
 
     /// Get project ID from metadata server
@@ -427,25 +940,328 @@ impl TokenProvider {
         paths
     }
 
-    /// Get cached token if still valid
-    fn get_cached_token() -> Option<String> {
-        let cache = TOKEN_CACHE.read().ok()?;
-        let cached = cache.as_ref()?;
+    /// Cached token of a credential, fresh or not
+    fn cached_token(key: &TokenKey) -> Option<AccessToken> {
+        TOKEN_CACHE.read().ok()?.get(key).cloned()
+    }
 
-        if cached.expires_at > Utc::now() {
-            Some(cached.token.clone())
-        } else {
-            None
+    /// Lock held while refreshing the token of a credential
+    fn refresh_lock(key: &TokenKey) -> Arc<tokio::sync::Mutex<()>> {
+        let mut locks = REFRESH_LOCKS.lock().unwrap_or_else(|e| e.into_inner());
+        locks.entry(key.clone()).or_default().clone()
+    }
+}
+
+/// Subject token from a file or URL body, plain text or a field of a JSON object
+fn parse_subject_token(raw: &str, format: Option<&SubjectTokenFormat>) -> Result<String> {
+    let token = match format {
+        Some(SubjectTokenFormat {
+            format_type: Some(format_type),
+            subject_token_field_name,
+        }) if format_type == "json" => {
+            let field = subject_token_field_name.as_deref().ok_or_else(|| {
+                anyhow!("Missing subject_token_field_name for a JSON subject token")
+            })?;
+            let value: serde_json::Value =
+                serde_json::from_str(raw).context("Failed to parse subject token JSON")?;
+            value
+                .get(field)
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("Subject token JSON has no string field '{}'", field))?
+                .to_string()
+        }
+        _ => raw.trim().to_string(),
+    };
+    if token.is_empty() {
+        return Err(anyhow!("Subject token is empty"));
+    }
+    Ok(token)
+}
+
+/// Form of the STS token exchange for an external account
+fn sts_exchange_params(
+    creds: &ExternalAccountCredentials,
+    subject_token: &str,
+) -> Vec<(&'static str, String)> {
+    let mut params = vec![
+        (
+            "grant_type",
+            "urn:ietf:params:oauth:grant-type:token-exchange".to_string(),
+        ),
+        ("audience", creds.audience.clone()),
+        ("scope", CLOUD_PLATFORM_SCOPE.to_string()),
+        (
+            "requested_token_type",
+            "urn:ietf:params:oauth:token-type:access_token".to_string(),
+        ),
+        ("subject_token", subject_token.to_string()),
+        ("subject_token_type", creds.subject_token_type.clone()),
+    ];
+    // Workforce pools bill a project unless the token is impersonated
+    if let (Some(project), None) = (
+        &creds.workforce_pool_user_project,
+        &creds.service_account_impersonation_url,
+    ) {
+        params.push((
+            "options",
+            serde_json::json!({ "userProject": project }).to_string(),
+        ));
+    }
+    params
+}
+
+/// `generateAccessToken` URL of a service account email
+fn generate_access_token_url(account: &str) -> String {
+    format!(
+        "{}/{}:generateAccessToken",
+        IAM_CREDENTIALS_URL,
+        service_account_name(account)
+    )
+}
+
+/// Resource name of a service account, as delegates are given to the API
+fn service_account_name(account: &str) -> String {
+    if account.starts_with("projects/") {
+        account.to_string()
+    } else {
+        format!("projects/-/serviceAccounts/{}", account)
+    }
+}
+
+/// Service account of a `generateAccessToken` URL, for logs and errors
+fn impersonated_account(url: &str) -> &str {
+    url.trim_end_matches(":generateAccessToken")
+        .rsplit('/')
+        .next()
+        .unwrap_or(url)
+}
+
+/// Request body of `generateAccessToken`
+fn generate_access_token_body(delegates: &[String]) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "scope": [CLOUD_PLATFORM_SCOPE],
+        "lifetime": "3600s",
+    });
+    if !delegates.is_empty() {
+        body["delegates"] = delegates
+            .iter()
+            .map(|d| service_account_name(d))
+            .collect::<Vec<_>>()
+            .into();
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_impersonation_chain() {
+        let config = CredentialConfig::default().impersonating("a@p.iam.gserviceaccount.com");
+        assert_eq!(
+            config.impersonate_service_account.as_deref(),
+            Some("a@p.iam.gserviceaccount.com")
+        );
+        assert!(config.delegates.is_empty());
+
+        // The last account is the target, the others delegate in order
+        let config = CredentialConfig::default().impersonating("a@p.iam, b@p.iam ,c@p.iam");
+        assert_eq!(
+            config.impersonate_service_account.as_deref(),
+            Some("c@p.iam")
+        );
+        assert_eq!(config.delegates, ["a@p.iam", "b@p.iam"]);
+
+        let body = generate_access_token_body(&config.delegates);
+        assert_eq!(
+            body["delegates"],
+            serde_json::json!([
+                "projects/-/serviceAccounts/a@p.iam",
+                "projects/-/serviceAccounts/b@p.iam"
+            ])
+        );
+        assert!(generate_access_token_body(&[]).get("delegates").is_none());
+    }
+
+    #[test]
+    fn test_impersonation_url() {
+        let url = generate_access_token_url("deployer@acme.iam.gserviceaccount.com");
+        assert_eq!(
+            url,
+            "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/deployer@acme.iam.gserviceaccount.com:generateAccessToken"
+        );
+        assert_eq!(
+            impersonated_account(&url),
+            "deployer@acme.iam.gserviceaccount.com"
+        );
+    }
+
+    #[test]
+    fn test_impersonated_credentials_file() {
+        let json = r#"{
+            "type": "impersonated_service_account",
+            "service_account_impersonation_url": "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/sa@p.iam.gserviceaccount.com:generateAccessToken",
+            "delegates": ["projects/-/serviceAccounts/hop@p.iam.gserviceaccount.com"],
+            "source_credentials": {
+                "type": "authorized_user",
+                "client_id": "id",
+                "client_secret": "secret",
+                "refresh_token": "token"
+            }
+        }"#;
+        let creds: ImpersonatedCredentials = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            impersonated_account(&creds.service_account_impersonation_url),
+            "sa@p.iam.gserviceaccount.com"
+        );
+        // Delegates already given as resource names are kept
+        assert_eq!(
+            generate_access_token_body(&creds.delegates)["delegates"][0],
+            "projects/-/serviceAccounts/hop@p.iam.gserviceaccount.com"
+        );
+        assert_eq!(creds.source_credentials["type"], "authorized_user");
+    }
+
+    #[test]
+    fn test_subject_token() {
+        assert_eq!(
+            parse_subject_token("  eyJhbGc.x.y\n", None).unwrap(),
+            "eyJhbGc.x.y"
+        );
+
+        let json_format = SubjectTokenFormat {
+            format_type: Some("json".to_string()),
+            subject_token_field_name: Some("access_token".to_string()),
+        };
+        assert_eq!(
+            parse_subject_token(
+                r#"{"access_token": "abc", "expires_in": 60}"#,
+                Some(&json_format)
+            )
+            .unwrap(),
+            "abc"
+        );
+        assert!(parse_subject_token(r#"{"token": "abc"}"#, Some(&json_format)).is_err());
+        assert!(parse_subject_token("", None).is_err());
+    }
+
+    #[test]
+    fn test_external_account() {
+        let json = r#"{
+            "type": "external_account",
+            "audience": "//iam.googleapis.com/projects/123/locations/global/workloadIdentityPools/ci/providers/github",
+            "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+            "token_url": "https://sts.googleapis.com/v1/token",
+            "credential_source": {
+                "url": "http://169.254.169.254/token",
+                "headers": {"Metadata": "true"},
+                "format": {"type": "json", "subject_token_field_name": "value"}
+            }
+        }"#;
+        let creds: ExternalAccountCredentials = serde_json::from_str(json).unwrap();
+        assert_eq!(creds.credential_source.headers["Metadata"], "true");
+
+        let params: HashMap<_, _> = sts_exchange_params(&creds, "subject").into_iter().collect();
+        assert_eq!(
+            params["grant_type"],
+            "urn:ietf:params:oauth:grant-type:token-exchange"
+        );
+        assert_eq!(params["audience"], creds.audience);
+        assert_eq!(params["subject_token"], "subject");
+        assert_eq!(
+            params["subject_token_type"],
+            "urn:ietf:params:oauth:token-type:jwt"
+        );
+        assert!(!params.contains_key("options"));
+    }
+
+    #[test]
+    fn test_workforce_pool_options() {
+        let json = r#"{
+            "type": "external_account",
+            "audience": "//iam.googleapis.com/locations/global/workforcePools/staff/providers/okta",
+            "subject_token_type": "urn:ietf:params:oauth:token-type:id_token",
+            "workforce_pool_user_project": "acme-billing",
+            "credential_source": {"file": "/var/run/token"}
+        }"#;
+        let mut creds: ExternalAccountCredentials = serde_json::from_str(json).unwrap();
+        let params: HashMap<_, _> = sts_exchange_params(&creds, "subject").into_iter().collect();
+        assert_eq!(params["options"], r#"{"userProject":"acme-billing"}"#);
+
+        // Impersonated tokens are billed to the service account's project
+        creds.service_account_impersonation_url = Some(generate_access_token_url("sa@acme.iam"));
+        let params: HashMap<_, _> = sts_exchange_params(&creds, "subject").into_iter().collect();
+        assert!(!params.contains_key("options"));
+    }
+
+    fn token(expires_in: Duration, identity: &str) -> AccessToken {
+        AccessToken {
+            token: "token".to_string(),
+            expires_at: Utc::now() + expires_in,
+            identity: Some(identity.to_string()),
+            source: AuthSource::KeyFile,
+            scopes: vec![CLOUD_PLATFORM_SCOPE.to_string()],
         }
     }
 
-    /// Cache a token with expiry
-    fn cache_token(token: &str, expires_at: chrono::DateTime<Utc>) {
+    #[test]
+    fn test_token_freshness() {
+        let t = token(Duration::hours(1), "a@example.com");
+        assert!(t.is_fresh() && t.is_valid());
+
+        // Within the margin the token still works but gets refreshed
+        let t = token(Duration::minutes(2), "a@example.com");
+        assert!(!t.is_fresh() && t.is_valid());
+
+        let t = token(Duration::minutes(-1), "a@example.com");
+        assert!(!t.is_fresh() && !t.is_valid());
+    }
+
+    #[test]
+    fn test_token_key() {
+        let file = CredentialConfig {
+            source: CredentialSource::File(PathBuf::from("/keys/test-token-key.json")),
+            ..Default::default()
+        };
+        let impersonated = file.clone().impersonating("viewer@acme.iam");
+        let delegated = file.clone().impersonating("hop@acme.iam,viewer@acme.iam");
+        let key = TokenKey::new(&file);
+        assert_ne!(key, TokenKey::new(&impersonated));
+        assert_ne!(TokenKey::new(&impersonated), TokenKey::new(&delegated));
+        assert_eq!(key, TokenKey::new(&file.clone()));
+
+        // Each identity keeps its own token
         if let Ok(mut cache) = TOKEN_CACHE.write() {
-            *cache = Some(CachedToken {
-                token: token.to_string(),
-                expires_at,
-            });
+            cache.insert(key.clone(), token(Duration::hours(1), "base@acme.iam"));
         }
+        assert_eq!(
+            TokenProvider::cached_token(&key)
+                .unwrap()
+                .identity
+                .as_deref(),
+            Some("base@acme.iam")
+        );
+        assert!(TokenProvider::cached_token(&TokenKey::new(&impersonated)).is_none());
+
+        // Refreshes of one credential share a lock
+        assert!(Arc::ptr_eq(
+            &TokenProvider::refresh_lock(&key),
+            &TokenProvider::refresh_lock(&key)
+        ));
+    }
+
+    #[test]
+    fn test_auth_source() {
+        assert_eq!(AuthSource::AdcUser.to_string(), "ADC user");
+        let source = AuthSource::Impersonation(Box::new(AuthSource::Metadata));
+        assert_eq!(source.to_string(), "impersonation via metadata");
+
+        let info: TokenInfo = serde_json::from_str(
+            r#"{"email": "me@example.com", "expires_in": "3599",
+                "scope": "openid https://www.googleapis.com/auth/cloud-platform"}"#,
+        )
+        .unwrap();
+        assert_eq!(info.scopes(), ["openid", CLOUD_PLATFORM_SCOPE]);
     }
 }
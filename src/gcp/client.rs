@@ -1,14 +1,47 @@
 use super::auth::TokenProvider;
 use anyhow::Result;
 use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use tracing::{debug, error, info, trace};
 
+/// Default cap on pages fetched by a single list call
+pub const DEFAULT_MAX_PAGES: usize = 20;
+
+/// Region value selecting every region of the project
+pub const ALL_REGIONS: &str = "-";
+
+/// Zone argument selecting every zone
+pub const ALL_ZONES: &str = "all";
+
 #[derive(Clone)]
 pub struct GcpClient {
     pub http: Client,
     pub project: String,
     pub zone: String,
+    /// Region of regional resources, independent of the zone; `ALL_REGIONS` lists all
     pub region: String,
+    /// List zonal resources across all zones through their aggregated endpoints
+    pub all_zones: bool,
+    /// Maximum pages followed when listing a paginated resource
+    pub max_pages: usize,
+    /// Regions of each project, fetched once
+    region_cache: Arc<Mutex<HashMap<String, Vec<ComputeLocation>>>>,
+    zone_cache: Arc<Mutex<HashMap<String, Vec<ComputeLocation>>>>,
+}
+
+/// Zone or region of a project, from the Compute API
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComputeLocation {
+    pub name: String,
+    /// UP or DOWN
+    pub status: String,
+}
+
+impl ComputeLocation {
+    pub fn is_up(&self) -> bool {
+        self.status == "UP"
+    }
 }
 
 impl GcpClient {
@@ -36,7 +69,7 @@ impl GcpClient {
         // Default zone
         let zone = zone.unwrap_or_else(|| "us-central1-a".to_string());
 
-        // Derive region from zone
+        // Start in the zone's region until one is chosen
         let region = derive_region(&zone);
 
         info!(
@@ -55,48 +88,70 @@ impl GcpClient {
             project,
             zone,
             region,
+            all_zones: false,
+            max_pages: DEFAULT_MAX_PAGES,
+            region_cache: Arc::default(),
+            zone_cache: Arc::default(),
         })
     }
 
-    /// List all projects accessible to the current user
-    pub async fn list_projects(&self) -> Result<Vec<String>> {
-        debug!("Listing GCP projects");
+    /// True if regional resources are listed across all regions
+    pub fn all_regions(&self) -> bool {
+        self.region == ALL_REGIONS
+    }
 
-        let token = TokenProvider::get_token().await?;
-        let url =
-            "https://cloudresourcemanager.googleapis.com/v1/projects?filter=lifecycleState:ACTIVE";
+    /// Regions of the project, from the Compute API
+    pub async fn list_regions(&self) -> Result<Vec<ComputeLocation>> {
+        self.list_compute_locations("regions", &self.region_cache)
+            .await
+    }
 
-        let res = self
-            .http
-            .get(url)
-            .header("Authorization", format!("Bearer {}", token))
-            .send()
-            .await?;
+    /// Zones of the project, from the Compute API
+    pub async fn list_zones(&self) -> Result<Vec<ComputeLocation>> {
+        self.list_compute_locations("zones", &self.zone_cache).await
+    }
 
-        let status = res.status();
-        if !status.is_success() {
-            let text = res.text().await?;
-            error!("Failed to list projects: {} - {}", status, text);
-            return Err(anyhow::anyhow!("Failed to list projects: {}", status));
+    /// List zones or regions sorted by name, cached per project
+    async fn list_compute_locations(
+        &self,
+        kind: &str,
+        cache: &Mutex<HashMap<String, Vec<ComputeLocation>>>,
+    ) -> Result<Vec<ComputeLocation>> {
+        if let Some(locations) = cache.lock().unwrap().get(&self.project) {
+            return Ok(locations.clone());
         }
 
-        let json: serde_json::Value = res.json().await?;
-
-        let projects: Vec<String> = json
-            .get("projects")
-            .and_then(|p| p.as_array())
+        let url = format!(
+            "https://compute.googleapis.com/compute/v1/projects/{}/{}",
+            self.project, kind
+        );
+        let json = self.request("GET", &url).await?;
+        let mut locations: Vec<ComputeLocation> = json
+            .get("items")
+            .and_then(|items| items.as_array())
             .map(|arr| {
                 arr.iter()
-                    .filter_map(|p| p.get("projectId").and_then(|id| id.as_str()))
-                    .map(|s| s.to_string())
+                    .filter_map(|l| {
+                        Some(ComputeLocation {
+                            name: l.get("name")?.as_str()?.to_string(),
+                            status: l
+                                .get("status")
+                                .and_then(|s| s.as_str())
+                                .unwrap_or_default()
+                                .to_string(),
+                        })
+                    })
                     .collect()
             })
             .unwrap_or_default();
+        locations.sort_by(|a, b| a.name.cmp(&b.name));
 
-        info!("Found {} projects", projects.len());
-        debug!("Projects: {:?}", projects);
-
-        Ok(projects)
+        info!("Found {} {}", locations.len(), kind);
+        cache
+            .lock()
+            .unwrap()
+            .insert(self.project.clone(), locations.clone());
+        Ok(locations)
     }
 
     /// Make an HTTP request to GCP API
@@ -148,13 +203,14 @@ impl GcpClient {
     }
 
     /// Make a request with a JSON body
-    #[allow(dead_code)]
     pub async fn request_with_body(
         &self,
         method: &str,
         url: &str,
         body: &serde_json::Value,
     ) -> Result<serde_json::Value> {
+        debug!("GCP API request with body: {} {}", method, url);
+
         let token = TokenProvider::get_token().await?;
 
         let req_method = match method.to_uppercase().as_str() {
@@ -176,6 +232,10 @@ impl GcpClient {
         if !res.status().is_success() {
             let status = res.status();
             let text = res.text().await?;
+            error!(
+                "GCP API Error {}: {} {}\nResponse: {}",
+                status, method, url, text
+            );
             return Err(anyhow::anyhow!("GCP API Error {}: {}", status, text));
         }
 
@@ -188,15 +248,31 @@ impl GcpClient {
         Ok(json)
     }
 
-    /// Update zone and derive region
+    /// Update zone, the region is left as is
     pub fn set_zone(&mut self, zone: &str) {
         self.zone = zone.to_string();
-        self.region = derive_region(zone);
+    }
+}
+
+/// Parse a region argument, "all" (or "-") selects every region
+pub fn parse_region(region: &str) -> String {
+    match region.trim() {
+        "all" | ALL_REGIONS => ALL_REGIONS.to_string(),
+        region => region.to_string(),
+    }
+}
+
+/// Region as shown to the user and stored in the config
+pub fn region_label(region: &str) -> &str {
+    if region == ALL_REGIONS {
+        "all"
+    } else {
+        region
     }
 }
 
 /// Derive region from zone (e.g., "us-central1-a" -> "us-central1")
-fn derive_region(zone: &str) -> String {
+pub fn derive_region(zone: &str) -> String {
     if let Some(idx) = zone.rfind('-') {
         zone[..idx].to_string()
     } else {
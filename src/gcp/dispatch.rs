@@ -1,68 +1,317 @@
 use super::client::GcpClient;
-use crate::resource::registry::ResourceDef;
+use crate::resource::registry::{extract_json_value, ActionDef, PaginationStyle, ResourceDef};
 use anyhow::Result;
 use serde_json::Value;
 use std::collections::HashMap;
-use tracing::{debug, info};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{debug, info, trace, warn};
+use url::Url;
+
+/// Progress of a paginated list call, reported after every page
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ListProgress {
+    /// Pages fetched so far
+    pub pages: usize,
+    /// Items collected so far
+    pub items: usize,
+    /// True if the page cap was hit while more pages were available
+    pub truncated: bool,
+}
+
+/// Concurrent list requests when listing every region one by one
+const REGION_CONCURRENCY: usize = 8;
 
 /// List resources using the resource definition
 /// parent_item: Optional parent item for sub-resources (provides context like {secret}, {cluster}, etc.)
+/// filter: Server-side filter expression, the definition's default is used when None
+/// on_page: Called after every fetched page so callers can show progress
+///
+/// With the client set to all regions, regional resources are listed with `-`
+/// as the region where the API supports it, otherwise region by region; items
+/// get a `location` field if they have none
 pub async fn list_resources(
     client: &GcpClient,
     resource: &ResourceDef,
     parent_item: Option<&Value>,
+    filter: Option<&str>,
+    on_page: &mut (dyn FnMut(ListProgress) + Send),
 ) -> Result<Vec<Value>> {
-    // Build extra context from parent item if available
-    let extra = parent_item.map(|item| {
-        let mut map = HashMap::new();
-        // Extract common fields from parent that might be needed in URL
-        // The full resource name (e.g., "projects/xxx/secrets/mysecret")
-        if let Some(name) = item.get("name").and_then(|v| v.as_str()) {
-            map.insert("secret".to_string(), name.to_string());
-            map.insert("parent".to_string(), name.to_string());
-            map.insert("cluster".to_string(), name.to_string());
-            map.insert("instance".to_string(), name.to_string());
-            map.insert("topic".to_string(), name.to_string());
-            map.insert("service".to_string(), name.to_string());
-        }
-        // Extract location if present
-        if let Some(location) = item.get("location").and_then(|v| v.as_str()) {
-            map.insert("location".to_string(), location.to_string());
+    // Sub-resources stay in their parent's region
+    let all_regions = client.all_regions() && resource.is_regional() && parent_item.is_none();
+    if all_regions && !resource.api.region_wildcard {
+        return list_each_region(client, resource, filter, on_page).await;
+    }
+
+    let mut items = list_location(client, resource, parent_item, filter, on_page).await?;
+    if all_regions {
+        for item in &mut items {
+            set_location(item, None);
         }
-        map
-    });
-
-    let url = interpolate_url(
-        &resource.api.base,
-        &resource.api.path,
-        client,
-        extra.as_ref(),
+    }
+    Ok(items)
+}
+
+/// List a regional resource in every region of the project concurrently,
+/// merged in region order; regions that fail are skipped unless all do
+async fn list_each_region(
+    client: &GcpClient,
+    resource: &ResourceDef,
+    filter: Option<&str>,
+    on_page: &mut (dyn FnMut(ListProgress) + Send),
+) -> Result<Vec<Value>> {
+    let regions: Vec<String> = client
+        .list_regions()
+        .await?
+        .into_iter()
+        .filter(|r| r.is_up())
+        .map(|r| r.name)
+        .collect();
+    debug!(
+        "Listing {} in {} regions",
+        resource.display_name,
+        regions.len()
     );
 
+    let semaphore = Arc::new(Semaphore::new(REGION_CONCURRENCY));
+    let resource = Arc::new(resource.clone());
+    let mut tasks = JoinSet::new();
+    for (index, region) in regions.iter().enumerate() {
+        let mut client = client.clone();
+        client.region = region.clone();
+        let resource = resource.clone();
+        let filter = filter.map(str::to_string);
+        let semaphore = semaphore.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let mut progress = ListProgress::default();
+            let result = list_location(&client, &resource, None, filter.as_deref(), &mut |p| {
+                progress = p
+            })
+            .await;
+            (index, result, progress)
+        });
+    }
+
+    // Progress is the sum over finished regions
+    let mut results: Vec<Option<Result<Vec<Value>>>> = regions.iter().map(|_| None).collect();
+    let mut total = ListProgress::default();
+    while let Some(joined) = tasks.join_next().await {
+        let (index, result, progress) = joined?;
+        total.pages += progress.pages;
+        total.items += progress.items;
+        total.truncated |= progress.truncated;
+        on_page(total);
+        results[index] = Some(result);
+    }
+
+    let mut items = Vec::new();
+    let mut first_error = None;
+    for (region, result) in regions.iter().zip(results) {
+        match result.expect("every region task reports back") {
+            Ok(listed) => items.extend(listed.into_iter().map(|mut item| {
+                set_location(&mut item, Some(region));
+                item
+            })),
+            Err(e) => {
+                warn!(
+                    "Listing {} in {} failed: {}",
+                    resource.display_name, region, e
+                );
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    match first_error {
+        Some(e) if items.is_empty() => Err(e),
+        _ => Ok(items),
+    }
+}
+
+/// Give an item a `location` field for the Location column, from its full
+/// resource name or else the region it was listed in
+fn set_location(item: &mut Value, region: Option<&str>) {
+    if item.get("location").is_some() {
+        return;
+    }
+    let location = item
+        .get("name")
+        .and_then(Value::as_str)
+        .and_then(location_from_name)
+        .or(region)
+        .map(str::to_string);
+    if let (Some(obj), Some(location)) = (item.as_object_mut(), location) {
+        obj.insert("location".to_string(), Value::String(location));
+    }
+}
+
+/// Location segment of a full resource name (projects/p/locations/l/...)
+fn location_from_name(name: &str) -> Option<&str> {
+    let mut segments = name.split('/');
+    segments.find(|s| *s == "locations")?;
+    segments.next().filter(|s| !s.is_empty())
+}
+
+/// List path and response path of a resource
+/// Zonal resources listed across all zones use their aggregated endpoint
+fn list_endpoint<'a>(
+    client: &GcpClient,
+    resource: &'a ResourceDef,
+    parent_item: Option<&Value>,
+) -> (&'a str, &'a str) {
+    match &resource.api.aggregated {
+        Some(agg) if client.all_zones && parent_item.is_none() => (&agg.path, &agg.response_path),
+        _ => (&resource.api.path, &resource.response_path),
+    }
+}
+
+/// List a resource in the client's project, zone and region
+async fn list_location(
+    client: &GcpClient,
+    resource: &ResourceDef,
+    parent_item: Option<&Value>,
+    filter: Option<&str>,
+    on_page: &mut (dyn FnMut(ListProgress) + Send),
+) -> Result<Vec<Value>> {
+    // Build extra context from parent item if available
+    let extra = parent_item.map(parent_placeholders);
+
+    let (path, response_path) = list_endpoint(client, resource, parent_item);
+    let mut url = interpolate_url(&resource.api.base, path, client, extra.as_ref());
+
+    // Server-side filter, kept on every page request
+    if let Some(def) = &resource.api.filter {
+        if let Some(expr) = filter.or(def.default.as_deref()).filter(|e| !e.is_empty()) {
+            url = with_query_param(&url, &def.param, expr);
+        }
+    }
+
     debug!("Listing resources: {} -> {}", resource.display_name, url);
 
-    let response = client.request(&resource.api.method, &url).await?;
+    let pagination = &resource.pagination;
+    let max_pages = client.max_pages.max(1);
+    let mut items = Vec::new();
+    let mut progress = ListProgress::default();
+    let mut page_url = url.clone();
+
+    loop {
+        let response = client.request(&resource.api.method, &page_url).await?;
+        items.extend(extract_items(&response, response_path));
 
-    let items = if resource.response_path.is_empty() {
-        if response.is_array() {
-            response.as_array().unwrap().clone()
+        progress.pages += 1;
+        progress.items = items.len();
+
+        let next_token = match pagination.style {
+            PaginationStyle::Token => next_page_token(&response, &pagination.token_field),
+            PaginationStyle::None => None,
+        };
+
+        let Some(token) = next_token else {
+            on_page(progress);
+            break;
+        };
+
+        if progress.pages >= max_pages {
+            warn!(
+                "Stopping {} listing after {} pages ({} items), more pages available",
+                resource.display_name, progress.pages, progress.items
+            );
+            progress.truncated = true;
+            on_page(progress);
+            break;
+        }
+
+        on_page(progress);
+        page_url = with_query_param(&url, &pagination.token_param, &token);
+        trace!("Fetching next page: {}", page_url);
+    }
+
+    info!(
+        "Listed {} {} items in {} page(s)",
+        items.len(),
+        resource.display_name,
+        progress.pages
+    );
+    Ok(items)
+}
+
+/// Placeholders a parent item provides to its sub-resources' URLs
+fn parent_placeholders(item: &Value) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    // Extract common fields from parent that might be needed in URL
+    // The full resource name (e.g., "projects/xxx/secrets/mysecret")
+    if let Some(name) = item.get("name").and_then(|v| v.as_str()) {
+        map.insert("secret".to_string(), name.to_string());
+        map.insert("parent".to_string(), name.to_string());
+        map.insert("cluster".to_string(), name.to_string());
+        map.insert("instance".to_string(), name.to_string());
+        map.insert("topic".to_string(), name.to_string());
+        map.insert("service".to_string(), name.to_string());
+    }
+    // Extract location if present
+    if let Some(location) = item.get("location").and_then(|v| v.as_str()) {
+        map.insert("location".to_string(), location.to_string());
+    }
+    // Sub-resources of a regional parent live in its region
+    if let Some(region) = item_region(item, "name") {
+        map.insert("region".to_string(), region);
+    }
+    map
+}
+
+/// Extract the item array from a single list response
+fn extract_items(response: &Value, response_path: &str) -> Vec<Value> {
+    if response_path.is_empty() {
+        if let Some(arr) = response.as_array() {
+            arr.clone()
         } else {
-            vec![response]
+            vec![response.clone()]
         }
-    } else if resource.response_path.contains(".*") {
+    } else if response_path.contains(".*") {
         // Handle aggregated list responses (e.g., "items.*.subnetworks")
         // Format: "items.*.fieldName" where items is a map of region -> { fieldName: [...] }
-        extract_aggregated_items(&response, &resource.response_path)
+        extract_aggregated_items(response, response_path)
     } else {
         response
-            .pointer(&format!("/{}", resource.response_path.replace('.', "/")))
+            .pointer(&format!("/{}", response_path.replace('.', "/")))
             .and_then(|v| v.as_array())
             .cloned()
             .unwrap_or_default()
-    };
+    }
+}
 
-    info!("Listed {} {} items", items.len(), resource.display_name);
-    Ok(items)
+/// Read the next page token from a response, ignoring empty tokens
+pub fn next_page_token(response: &Value, token_field: &str) -> Option<String> {
+    response
+        .pointer(&format!("/{}", token_field.replace('.', "/")))
+        .and_then(|v| v.as_str())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_string())
+}
+
+/// Set a query parameter on a URL, keeping any query string already in the path
+pub fn with_query_param(url: &str, key: &str, value: &str) -> String {
+    match Url::parse(url) {
+        Ok(mut parsed) => {
+            let pairs: Vec<(String, String)> = parsed
+                .query_pairs()
+                .filter(|(k, _)| k != key)
+                .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                .collect();
+            parsed
+                .query_pairs_mut()
+                .clear()
+                .extend_pairs(pairs)
+                .append_pair(key, value);
+            parsed.to_string()
+        }
+        Err(_) => {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            format!("{}{}{}={}", url, separator, key, value)
+        }
+    }
 }
 
 /// Extract items from aggregated list response
@@ -93,11 +342,13 @@ fn extract_aggregated_items(response: &Value, path: &str) -> Vec<Value> {
 }
 
 /// Execute an action on a resource
+/// params: Validated values for the action's input parameters, keyed by param name
 pub async fn execute_action(
     client: &GcpClient,
     resource: &ResourceDef,
     action_index: usize,
     item: &Value,
+    params: &HashMap<String, Value>,
 ) -> Result<Value> {
     let action = resource
         .actions
@@ -109,13 +360,109 @@ pub async fn execute_action(
         action.display_name, resource.display_name
     );
 
-    // Build extra placeholders from the item
+    let extra = action_placeholders(resource, item, params);
+    let url = interpolate_url(&resource.api.base, &action.api.path, client, Some(&extra));
+    debug!("Action URL: {} {}", action.api.method, url);
+
+    match &action.body {
+        Some(template) => {
+            let body = render_body(template, params, &extra);
+            debug!("Action body: {}", body);
+            client
+                .request_with_body(&action.api.method, &url, &body)
+                .await
+        }
+        None => client.request(&action.api.method, &url).await,
+    }
+}
+
+/// Placeholders of an action: the item's fields, zone and region, then its params
+fn action_placeholders(
+    resource: &ResourceDef,
+    item: &Value,
+    params: &HashMap<String, Value>,
+) -> HashMap<String, String> {
+    let mut extra = item_placeholders(resource, item);
+
+    // Parameter values can also be used as path placeholders
+    for (key, value) in params {
+        extra.insert(key.clone(), param_to_string(value));
+    }
+    extra
+}
+
+/// Fetch the full resource for an item through the resource's `get` API
+/// Returns None if the resource doesn't declare one
+/// parent_item: Optional parent item for sub-resources (see `list_resources`)
+pub async fn describe_resource(
+    client: &GcpClient,
+    resource: &ResourceDef,
+    item: &Value,
+    parent_item: Option<&Value>,
+) -> Result<Option<Value>> {
+    let Some(url) = item_url(client, resource, item, parent_item) else {
+        return Ok(None);
+    };
+    debug!("Describing {}: {}", resource.display_name, url);
+
+    client.request("GET", &url).await.map(Some)
+}
+
+/// API URL of a single item, if the resource defines a get request
+pub fn item_url(
+    client: &GcpClient,
+    resource: &ResourceDef,
+    item: &Value,
+    parent_item: Option<&Value>,
+) -> Option<String> {
+    let get = resource.get.as_ref()?;
+
+    let mut extra = parent_item.map(parent_placeholders).unwrap_or_default();
+    extra.extend(item_placeholders(resource, item));
+
+    let url = interpolate_url(&resource.api.base, &get.path, client, Some(&extra));
+    Some(resolve_item_fields(&url, item))
+}
+
+/// Cloud Console URL of an item, if the resource defines one
+pub fn console_url(
+    client: &GcpClient,
+    resource: &ResourceDef,
+    item: &Value,
+    parent_item: Option<&Value>,
+) -> Option<String> {
+    let template = resource.console_url.as_ref()?;
+
+    let mut extra = parent_item.map(parent_placeholders).unwrap_or_default();
+    extra.extend(item_placeholders(resource, item));
+
+    // Console pages take the short name and location rather than the full
+    // resource name (projects/p/locations/l/services/s)
+    let name = item
+        .get(&resource.name_field)
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if let Some(short) = name.rsplit('/').next().filter(|s| !s.is_empty()) {
+        extra.insert("short_name".to_string(), short.to_string());
+    }
+    if let Some(location) = location_from_name(name) {
+        extra
+            .entry("location".to_string())
+            .or_insert_with(|| location.to_string());
+    }
+
+    let url = fill_placeholders(template, client, Some(&extra));
+    Some(resolve_item_fields(&url, item))
+}
+
+/// Placeholders an item provides to action and get URLs (name, id, zone, region)
+fn item_placeholders(resource: &ResourceDef, item: &Value) -> HashMap<String, String> {
     let mut extra = HashMap::new();
 
     // Add name from the item
     if let Some(name) = item.get(&resource.name_field).and_then(|v| v.as_str()) {
         extra.insert("name".to_string(), name.to_string());
-        debug!("Action target name: {}", name);
+        debug!("Target name: {}", name);
     }
 
     // Add id from the item
@@ -131,17 +478,122 @@ pub async fn execute_action(
         }
     }
 
-    // Extract region from item if present (for regional resources)
+    // Region of regional resources, needed when listing all regions
+    if let Some(region) = item_region(item, &resource.name_field) {
+        extra.insert("region".to_string(), region);
+    }
+
+    extra
+}
+
+/// Region of an item: its `region` field (compute resources), the location in
+/// its full resource name, or a `location` field
+fn item_region(item: &Value, name_field: &str) -> Option<String> {
     if let Some(region_url) = item.get("region").and_then(|v| v.as_str()) {
-        if let Some(region) = region_url.split('/').next_back() {
-            extra.insert("region".to_string(), region.to_string());
+        // Region URLs look like: "https://www.googleapis.com/compute/v1/projects/PROJECT/regions/REGION"
+        return region_url.split('/').next_back().map(str::to_string);
+    }
+    item.get(name_field)
+        .and_then(Value::as_str)
+        .and_then(location_from_name)
+        .or_else(|| item.get("location").and_then(Value::as_str))
+        .map(str::to_string)
+}
+
+/// Replace `{field}` placeholders left in a URL with the item's field values
+fn resolve_item_fields(url: &str, item: &Value) -> String {
+    let mut result = String::new();
+    let mut rest = url;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let field = &rest[start + 1..start + len];
+        let value = extract_json_value(item, field);
+        if value == "-" {
+            // Leave unknown placeholders visible in the URL for debugging
+            result.push_str(&rest[start..=start + len]);
+        } else {
+            result.push_str(&value);
         }
+        rest = &rest[start + len + 1..];
     }
+    result.push_str(rest);
+    result
+}
 
-    let url = interpolate_url(&resource.api.base, &action.api.path, client, Some(&extra));
-    debug!("Action URL: {} {}", action.api.method, url);
+/// Render an action body template
+/// A string that is exactly "{param}" is replaced by the typed parameter value
+/// (so integers stay numbers), other placeholders are substituted as text
+fn render_body(
+    template: &Value,
+    params: &HashMap<String, Value>,
+    extra: &HashMap<String, String>,
+) -> Value {
+    match template {
+        Value::String(s) => {
+            if let Some(key) = s.strip_prefix('{').and_then(|k| k.strip_suffix('}')) {
+                if let Some(value) = params.get(key) {
+                    return value.clone();
+                }
+            }
+            let mut result = s.clone();
+            for (key, value) in extra {
+                result = result.replace(&format!("{{{}}}", key), value);
+            }
+            Value::String(result)
+        }
+        Value::Array(arr) => {
+            Value::Array(arr.iter().map(|v| render_body(v, params, extra)).collect())
+        }
+        Value::Object(map) => Value::Object(
+            map.iter()
+                // Optional params left empty are dropped from the body
+                .filter(|(_, v)| !is_empty_param(v, params))
+                .map(|(k, v)| (k.clone(), render_body(v, params, extra)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
 
-    client.request(&action.api.method, &url).await
+/// Confirmation text of an action on one item, with `{name}`, `{id}` and the
+/// action's `{param}` placeholders filled in; None if the action needs no confirmation
+pub fn confirm_message(
+    resource: &ResourceDef,
+    action: &ActionDef,
+    item: &Value,
+    params: &HashMap<String, Value>,
+) -> Option<String> {
+    let confirm = action.confirm.as_ref()?;
+    let mut message = confirm
+        .message
+        .replace("{name}", &extract_json_value(item, &resource.name_field))
+        .replace("{id}", &extract_json_value(item, &resource.id_field));
+    for (key, value) in params {
+        message = message.replace(&format!("{{{}}}", key), &param_to_string(value));
+    }
+    Some(message)
+}
+
+/// True if the template value is a "{param}" placeholder for a param with no value
+fn is_empty_param(template: &Value, params: &HashMap<String, Value>) -> bool {
+    template
+        .as_str()
+        .and_then(|s| s.strip_prefix('{'))
+        .and_then(|s| s.strip_suffix('}'))
+        .and_then(|key| params.get(key))
+        .is_some_and(|v| v.is_null())
+}
+
+/// Plain-text form of a parameter value for URL and text substitution
+fn param_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
 }
 
 /// Interpolate URL placeholders with client context and extra values
@@ -157,28 +609,303 @@ fn interpolate_url(
         path.trim_start_matches('/')
     );
 
-    let mut result = full_path
-        .replace("{project}", &client.project)
-        .replace("{zone}", &client.zone)
-        .replace("{region}", &derive_region_from_zone(&client.zone));
+    fill_placeholders(&full_path, client, extra)
+}
 
-    // Apply extra placeholders if provided
+/// Replace `{key}` placeholders with the client's project, zone and region and
+/// the extra values. Extras win: an item listed across zones or regions is
+/// addressed in its own location, not the current one
+fn fill_placeholders(
+    template: &str,
+    client: &GcpClient,
+    extra: Option<&HashMap<String, String>>,
+) -> String {
+    let mut values: HashMap<&str, &str> = HashMap::from([
+        ("project", client.project.as_str()),
+        ("zone", client.zone.as_str()),
+        ("region", client.region.as_str()),
+    ]);
     if let Some(extra) = extra {
-        for (key, value) in extra {
-            result = result.replace(&format!("{{{}}}", key), value);
-        }
+        values.extend(extra.iter().map(|(k, v)| (k.as_str(), v.as_str())));
     }
 
+    let mut result = template.to_string();
+    for (key, value) in values {
+        result = result.replace(&format!("{{{}}}", key), value);
+    }
     result
 }
 
-/// Derive region from zone (e.g., "us-central1-a" -> "us-central1")
-fn derive_region_from_zone(zone: &str) -> String {
-    // Zone format: region-zone (e.g., us-central1-a, europe-west1-b)
-    // Region is everything except the last part after the last hyphen
-    if let Some(idx) = zone.rfind('-') {
-        zone[..idx].to_string()
-    } else {
-        zone.to_string()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_next_page_token() {
+        let response = json!({"items": [], "nextPageToken": "abc"});
+        assert_eq!(
+            next_page_token(&response, "nextPageToken"),
+            Some("abc".to_string())
+        );
+
+        let nested = json!({"metadata": {"continue": "xyz"}});
+        assert_eq!(
+            next_page_token(&nested, "metadata.continue"),
+            Some("xyz".to_string())
+        );
+
+        let empty = json!({"nextPageToken": ""});
+        assert_eq!(next_page_token(&empty, "nextPageToken"), None);
+        assert_eq!(next_page_token(&json!({}), "nextPageToken"), None);
+    }
+
+    #[test]
+    fn test_with_query_param() {
+        assert_eq!(
+            with_query_param("https://example.com/v1/items", "pageToken", "t1"),
+            "https://example.com/v1/items?pageToken=t1"
+        );
+        assert_eq!(
+            with_query_param(
+                "https://example.com/v1/jobs?filter=ACTIVE",
+                "pageToken",
+                "t2"
+            ),
+            "https://example.com/v1/jobs?filter=ACTIVE&pageToken=t2"
+        );
+        // Existing token is replaced, not duplicated
+        assert_eq!(
+            with_query_param(
+                "https://example.com/v1/jobs?pageToken=old&pageSize=50",
+                "pageToken",
+                "new"
+            ),
+            "https://example.com/v1/jobs?pageSize=50&pageToken=new"
+        );
+    }
+
+    #[test]
+    fn test_render_body() {
+        let template = json!({
+            "sizeGb": "{sizeGb}",
+            "name": "{snapshotName}",
+            "description": "Snapshot of {name}",
+            "labels": ["{name}"],
+            "disabled": true,
+            "branchName": "{branch}"
+        });
+        let mut params = HashMap::new();
+        params.insert("sizeGb".to_string(), json!(200));
+        params.insert("snapshotName".to_string(), json!("disk-1-snap"));
+        params.insert("branch".to_string(), Value::Null);
+        let mut extra = HashMap::new();
+        extra.insert("name".to_string(), "disk-1".to_string());
+
+        let body = render_body(&template, &params, &extra);
+        assert_eq!(
+            body,
+            json!({
+                "sizeGb": 200,
+                "name": "disk-1-snap",
+                "description": "Snapshot of disk-1",
+                "labels": ["disk-1"],
+                "disabled": true
+            })
+        );
+    }
+
+    #[test]
+    fn test_extract_items() {
+        let response = json!({"items": [{"name": "a"}, {"name": "b"}]});
+        assert_eq!(extract_items(&response, "items").len(), 2);
+
+        let aggregated = json!({
+            "items": {
+                "regions/us-central1": {"subnetworks": [{"name": "a"}]},
+                "regions/europe-west1": {"subnetworks": [{"name": "b"}, {"name": "c"}]},
+                "regions/asia-east1": {"warning": {"code": "NO_RESULTS_ON_PAGE"}}
+            }
+        });
+        assert_eq!(extract_items(&aggregated, "items.*.subnetworks").len(), 3);
+
+        assert!(extract_items(&json!({}), "items").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_item_fields() {
+        let table = json!({
+            "tableReference": {"projectId": "p", "datasetId": "ds", "tableId": "events"}
+        });
+        assert_eq!(
+            resolve_item_fields(
+                "https://x/projects/p/datasets/{tableReference.datasetId}/tables/{tableReference.tableId}",
+                &table
+            ),
+            "https://x/projects/p/datasets/ds/tables/events"
+        );
+
+        // Unknown fields stay as placeholders
+        assert_eq!(
+            resolve_item_fields("https://x/{missing}/y", &table),
+            "https://x/{missing}/y"
+        );
+    }
+
+    #[test]
+    fn test_item_location() {
+        let service = json!({"name": "projects/p/locations/europe-west1/services/web"});
+        assert_eq!(
+            location_from_name(service["name"].as_str().unwrap()),
+            Some("europe-west1")
+        );
+        assert_eq!(location_from_name("projects/p/secrets/s"), None);
+        assert_eq!(
+            item_region(&service, "name").as_deref(),
+            Some("europe-west1")
+        );
+
+        // Compute resources carry a region URL
+        let rule = json!({"name": "fr", "region": "https://x/projects/p/regions/us-east1"});
+        assert_eq!(item_region(&rule, "name").as_deref(), Some("us-east1"));
+
+        // The name wins over the region an item was listed in
+        let mut item = service.clone();
+        set_location(&mut item, Some("us-central1"));
+        assert_eq!(item["location"], "europe-west1");
+
+        let mut item = json!({"clusterName": "c"});
+        set_location(&mut item, Some("us-central1"));
+        assert_eq!(item["location"], "us-central1");
+
+        // Existing locations are kept
+        let mut item = json!({"name": "c", "location": "us-central1-a"});
+        set_location(&mut item, Some("us-central1"));
+        assert_eq!(item["location"], "us-central1-a");
+    }
+
+    #[tokio::test]
+    async fn test_console_url() {
+        let client = GcpClient::new(None, Some("test-project".to_string()))
+            .await
+            .unwrap();
+        let url = |key: &str, item: Value| {
+            let resource = crate::resource::registry::get_resource(key).unwrap();
+            console_url(&client, resource, &item, None)
+        };
+
+        // The item's own zone wins over the current one
+        assert_eq!(
+            url("vm-instances", json!({"name": "vm-1", "zone": "https://x/zones/europe-west1-b"}))
+                .as_deref(),
+            Some("https://console.cloud.google.com/compute/instancesDetail/zones/europe-west1-b/instances/vm-1?project=test-project")
+        );
+        // Full resource names are split into location and short name
+        assert_eq!(
+            url("cloudrun-services", json!({"name": "projects/p/locations/us-east1/services/web"}))
+                .as_deref(),
+            Some("https://console.cloud.google.com/run/detail/us-east1/web/metrics?project=test-project")
+        );
+        assert_eq!(url("machine-types", json!({"name": "e2-small"})), None);
+    }
+
+    #[tokio::test]
+    async fn test_fill_placeholders() {
+        let client = GcpClient::new(None, Some("test-project".to_string()))
+            .await
+            .unwrap();
+        let template = "projects/{project}/zones/{zone}/instances/{name}";
+
+        // Without item values, the current location is used
+        let extra = HashMap::from([("name".to_string(), "vm-1".to_string())]);
+        assert_eq!(
+            fill_placeholders(template, &client, Some(&extra)),
+            "projects/test-project/zones/us-central1-a/instances/vm-1"
+        );
+
+        // An item's own zone and region win over the current ones
+        let mut extra = extra;
+        extra.insert("zone".to_string(), "europe-west1-b".to_string());
+        extra.insert("region".to_string(), "europe-west1".to_string());
+        assert_eq!(
+            fill_placeholders(template, &client, Some(&extra)),
+            "projects/test-project/zones/europe-west1-b/instances/vm-1"
+        );
+        assert_eq!(
+            fill_placeholders("regions/{region}/{unknown}", &client, Some(&extra)),
+            "regions/europe-west1/{unknown}"
+        );
+        assert_eq!(
+            fill_placeholders("regions/{region}", &client, None),
+            format!("regions/{}", client.region)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_all_zones_endpoints() {
+        let mut client = GcpClient::new(None, Some("test-project".to_string()))
+            .await
+            .unwrap();
+        let vms = crate::resource::registry::get_resource("vm-instances").unwrap();
+
+        assert_eq!(
+            list_endpoint(&client, vms, None),
+            ("projects/{project}/zones/{zone}/instances", "items")
+        );
+
+        client.all_zones = true;
+        assert_eq!(
+            list_endpoint(&client, vms, None),
+            (
+                "projects/{project}/aggregated/instances",
+                "items.*.instances"
+            )
+        );
+        // Sub-resources stay in their parent's zone
+        let parent = json!({"name": "vm-1"});
+        assert_eq!(list_endpoint(&client, vms, Some(&parent)).1, "items");
+
+        // Actions run in the item's zone, not the client's
+        let item = json!({
+            "name": "vm-1",
+            "zone": "https://www.googleapis.com/compute/v1/projects/test-project/zones/europe-west1-b"
+        });
+        let stop = vms
+            .actions
+            .iter()
+            .find(|a| a.display_name == "Stop")
+            .unwrap();
+        let extra = action_placeholders(vms, &item, &HashMap::new());
+        assert_eq!(
+            interpolate_url(&vms.api.base, &stop.api.path, &client, Some(&extra)),
+            "https://compute.googleapis.com/compute/v1/projects/test-project/zones/europe-west1-b/instances/vm-1/stop"
+        );
+    }
+
+    #[test]
+    fn test_confirm_message() {
+        let vms = crate::resource::registry::get_resource("vm-instances").unwrap();
+        let mut action = vms
+            .actions
+            .iter()
+            .find(|a| a.display_name == "Stop")
+            .unwrap()
+            .clone();
+        let item = json!({"name": "vm-1", "id": "42"});
+        let params = HashMap::from([("size".to_string(), json!(20))]);
+
+        assert_eq!(
+            confirm_message(vms, &action, &item, &params).as_deref(),
+            Some("Stop instance 'vm-1'?")
+        );
+
+        action.confirm.as_mut().unwrap().message = "Resize {id} to {size} GB?".to_string();
+        assert_eq!(
+            confirm_message(vms, &action, &item, &params).as_deref(),
+            Some("Resize 42 to 20 GB?")
+        );
+
+        action.confirm = None;
+        assert!(confirm_message(vms, &action, &item, &params).is_none());
     }
 }
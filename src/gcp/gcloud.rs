@@ -0,0 +1,165 @@
+//! gcloud CLI configurations
+//!
+//! Reads the named configurations gcloud keeps in
+//! `~/.config/gcloud/configurations/config_<name>` and the active one from
+//! `active_config`, so tgcp starts where gcloud points.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use tracing::debug;
+
+/// Properties of a gcloud configuration that tgcp uses
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GcloudConfig {
+    pub name: String,
+    /// `core/account`
+    pub account: Option<String>,
+    /// `core/project`
+    pub project: Option<String>,
+    /// `compute/zone`
+    pub zone: Option<String>,
+    /// `compute/region`
+    pub region: Option<String>,
+    /// `auth/impersonate_service_account`
+    pub impersonate_service_account: Option<String>,
+}
+
+impl GcloudConfig {
+    /// Parse the INI file of a configuration
+    pub fn parse(name: &str, contents: &str) -> Self {
+        let mut config = Self {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let mut section = String::new();
+
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name.trim().to_string();
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = Some(value.trim().to_string()).filter(|v| !v.is_empty());
+            match (section.as_str(), key.trim()) {
+                ("core", "account") => config.account = value,
+                ("core", "project") => config.project = value,
+                ("compute", "zone") => config.zone = value,
+                ("compute", "region") => config.region = value,
+                ("auth", "impersonate_service_account") => {
+                    config.impersonate_service_account = value
+                }
+                _ => {}
+            }
+        }
+        config
+    }
+
+    /// Credentials gcloud stored for the configuration's account, if any
+    pub fn credentials_file(&self) -> Option<PathBuf> {
+        let path = config_dir()?
+            .join("legacy_credentials")
+            .join(self.account.as_ref()?)
+            .join("adc.json");
+        path.exists().then_some(path)
+    }
+}
+
+/// gcloud's config directory (`CLOUDSDK_CONFIG` or the platform default)
+pub fn config_dir() -> Option<PathBuf> {
+    if let Ok(dir) = env::var("CLOUDSDK_CONFIG") {
+        return Some(PathBuf::from(dir));
+    }
+    #[cfg(windows)]
+    if let Ok(appdata) = env::var("APPDATA") {
+        return Some(PathBuf::from(appdata).join("gcloud"));
+    }
+    dirs::home_dir().map(|home| home.join(".config").join("gcloud"))
+}
+
+/// Name of the active configuration (`CLOUDSDK_ACTIVE_CONFIG_NAME` or `active_config`)
+pub fn active_name() -> Option<String> {
+    if let Ok(name) = env::var("CLOUDSDK_ACTIVE_CONFIG_NAME") {
+        return Some(name);
+    }
+    let contents = fs::read_to_string(config_dir()?.join("active_config")).ok()?;
+    Some(contents.trim().to_string()).filter(|n| !n.is_empty())
+}
+
+/// Load a configuration by name
+pub fn load(name: &str) -> Option<GcloudConfig> {
+    let path = config_dir()?
+        .join("configurations")
+        .join(format!("config_{}", name));
+    let contents = fs::read_to_string(&path).ok()?;
+    debug!("Loaded gcloud configuration {:?}", path);
+    Some(GcloudConfig::parse(name, &contents))
+}
+
+/// The active configuration, if gcloud is set up
+pub fn active() -> Option<GcloudConfig> {
+    load(&active_name()?)
+}
+
+/// All configurations, sorted by name
+pub fn list() -> Vec<GcloudConfig> {
+    let Some(dir) = config_dir().map(|d| d.join("configurations")) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            e.file_name()
+                .to_str()?
+                .strip_prefix("config_")
+                .map(|n| n.to_string())
+        })
+        .collect();
+    names.sort();
+    names.iter().filter_map(|name| load(name)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let contents = "\
+[core]
+account = dev@example.com
+project = acme-dev
+disable_usage_reporting = True
+
+# comment
+[compute]
+zone = europe-west1-b
+region =
+
+[auth]
+impersonate_service_account = viewer@acme-dev.iam.gserviceaccount.com
+";
+        let config = GcloudConfig::parse("dev", contents);
+        assert_eq!(config.name, "dev");
+        assert_eq!(config.account.as_deref(), Some("dev@example.com"));
+        assert_eq!(config.project.as_deref(), Some("acme-dev"));
+        assert_eq!(config.zone.as_deref(), Some("europe-west1-b"));
+        // Empty values are unset
+        assert_eq!(config.region, None);
+        assert_eq!(
+            config.impersonate_service_account.as_deref(),
+            Some("viewer@acme-dev.iam.gserviceaccount.com")
+        );
+    }
+}
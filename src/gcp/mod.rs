@@ -1,3 +1,6 @@
 pub mod auth;
 pub mod client;
 pub mod dispatch;
+pub mod gcloud;
+pub mod operations;
+pub mod resourcemanager;
@@ -0,0 +1,413 @@
+//! Long-running operation tracking
+//!
+//! Mutating GCP calls usually return an operation instead of the final resource.
+//! This module recognises the operation formats of the different API families
+//! and polls them in the background until they finish.
+
+use super::client::GcpClient;
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::{debug, info, warn};
+
+/// Delay between two polls of the same operation
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Give up polling after this long
+const POLL_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+/// Consecutive poll errors tolerated before the operation is marked failed
+const MAX_POLL_ERRORS: usize = 5;
+
+/// API family an operation belongs to (decides how its status is read)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OperationKind {
+    /// Compute Engine zonal, regional or global operation
+    Compute,
+    /// Cloud SQL Admin operation
+    Sql,
+    /// GKE operation
+    Container,
+    /// google.longrunning.Operation (`operations/...`, `done` flag)
+    LongRunning,
+}
+
+/// Reference to an operation returned by an action
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationRef {
+    pub kind: OperationKind,
+    /// Operation name as returned by the API
+    pub name: String,
+    /// URL polled for status
+    pub poll_url: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OperationState {
+    Running,
+    Done,
+    Failed,
+}
+
+/// Status of an operation at a point in time
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationStatus {
+    pub state: OperationState,
+    /// Progress percentage if the API reports one
+    pub progress: Option<u8>,
+    /// Error payload for failed operations
+    pub error: Option<Value>,
+}
+
+impl OperationStatus {
+    pub fn running() -> Self {
+        Self {
+            state: OperationState::Running,
+            progress: None,
+            error: None,
+        }
+    }
+
+    fn failed(message: &str) -> Self {
+        Self {
+            state: OperationState::Failed,
+            progress: None,
+            error: Some(serde_json::json!({ "message": message })),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.state != OperationState::Running
+    }
+}
+
+/// Status change sent from a poller task to the app
+#[derive(Debug, Clone)]
+pub struct OperationUpdate {
+    pub id: usize,
+    pub status: OperationStatus,
+}
+
+/// Operation started by an action, tracked until it finishes
+#[derive(Debug, Clone)]
+pub struct TrackedOperation {
+    pub id: usize,
+    /// Action and target, e.g. "Stop 'vm-1'"
+    pub description: String,
+    /// Resource the action was run on
+    pub resource_key: String,
+    pub op: OperationRef,
+    pub status: OperationStatus,
+    pub started: Instant,
+    pub finished: Option<Instant>,
+}
+
+impl TrackedOperation {
+    /// Time since start, or total duration once finished
+    pub fn elapsed(&self) -> Duration {
+        self.finished
+            .map(|f| f.duration_since(self.started))
+            .unwrap_or_else(|| self.started.elapsed())
+    }
+}
+
+/// Recognise an operation in an action response
+/// base: API base URL of the resource, used to resolve relative LRO names
+pub fn detect_operation(base: &str, response: &Value) -> Option<OperationRef> {
+    let name = response
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let self_link = response.get("selfLink").and_then(|v| v.as_str());
+
+    match response.get("kind").and_then(|v| v.as_str()) {
+        Some("compute#operation") => {
+            return self_link.map(|link| OperationRef {
+                kind: OperationKind::Compute,
+                name,
+                poll_url: link.to_string(),
+            });
+        }
+        Some("sql#operation") => {
+            return self_link.map(|link| OperationRef {
+                kind: OperationKind::Sql,
+                name,
+                poll_url: link.to_string(),
+            });
+        }
+        _ => {}
+    }
+
+    // GKE operations carry an operationType and a selfLink to poll
+    if response.get("operationType").is_some() && response.get("status").is_some() {
+        if let Some(link) = self_link.filter(|l| l.contains("/operations/")) {
+            return Some(OperationRef {
+                kind: OperationKind::Container,
+                name,
+                poll_url: link.to_string(),
+            });
+        }
+    }
+
+    // google.longrunning.Operation: name under operations/, `done` is omitted while running
+    let is_lro_name = name.starts_with("operations/") || name.contains("/operations/");
+    let looks_like_lro = response.get("done").is_some()
+        || response.get("metadata").is_some()
+        || response.get("response").is_some();
+    if is_lro_name && looks_like_lro {
+        let poll_url = if name.starts_with("https://") {
+            name.clone()
+        } else {
+            format!("{}/{}", base.trim_end_matches('/'), name)
+        };
+        return Some(OperationRef {
+            kind: OperationKind::LongRunning,
+            name,
+            poll_url,
+        });
+    }
+
+    None
+}
+
+/// Read the status of an operation response
+pub fn parse_status(kind: OperationKind, response: &Value) -> OperationStatus {
+    match kind {
+        OperationKind::Compute | OperationKind::Sql | OperationKind::Container => {
+            let status = response
+                .get("status")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            let progress = response
+                .get("progress")
+                .and_then(|v| v.as_u64())
+                .map(|p| p.min(100) as u8);
+            let error = response.get("error").cloned().or_else(|| {
+                // GKE reports failures through statusMessage only
+                response
+                    .get("statusMessage")
+                    .and_then(|v| v.as_str())
+                    .filter(|m| !m.is_empty() && kind == OperationKind::Container)
+                    .map(|m| serde_json::json!({ "message": m }))
+            });
+
+            let state = match status {
+                "DONE" if error.is_some() => OperationState::Failed,
+                "DONE" => OperationState::Done,
+                "ABORTING" => OperationState::Failed,
+                _ => OperationState::Running,
+            };
+
+            OperationStatus {
+                progress: if state == OperationState::Done {
+                    Some(100)
+                } else {
+                    progress
+                },
+                error: if state == OperationState::Failed {
+                    error
+                } else {
+                    None
+                },
+                state,
+            }
+        }
+        OperationKind::LongRunning => {
+            let done = response
+                .get("done")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let error = response.get("error").cloned();
+            // Some APIs report progress in metadata (e.g., Cloud Build, Spanner)
+            let progress = response
+                .pointer("/metadata/progressPercent")
+                .or_else(|| response.pointer("/metadata/progress/progressPercent"))
+                .and_then(|v| v.as_u64())
+                .map(|p| p.min(100) as u8);
+
+            match (done, error) {
+                (true, Some(error)) => OperationStatus {
+                    state: OperationState::Failed,
+                    progress,
+                    error: Some(error),
+                },
+                (true, None) => OperationStatus {
+                    state: OperationState::Done,
+                    progress: Some(100),
+                    error: None,
+                },
+                (false, _) => OperationStatus {
+                    state: OperationState::Running,
+                    progress,
+                    error: None,
+                },
+            }
+        }
+    }
+}
+
+/// Poll an operation in a background task until it finishes
+/// Every status change is sent to `tx`; the task ends when the operation
+/// finishes, times out, or the receiver is dropped
+pub fn spawn_poller(
+    client: GcpClient,
+    id: usize,
+    op: OperationRef,
+    tx: UnboundedSender<OperationUpdate>,
+) {
+    tokio::spawn(async move {
+        let started = Instant::now();
+        let mut last_status = OperationStatus::running();
+        let mut errors = 0;
+
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+
+            let status = if started.elapsed() > POLL_TIMEOUT {
+                warn!("Operation {} timed out", op.name);
+                OperationStatus::failed("Timed out waiting for operation to finish")
+            } else {
+                match client.request("GET", &op.poll_url).await {
+                    Ok(response) => {
+                        errors = 0;
+                        parse_status(op.kind, &response)
+                    }
+                    Err(e) => {
+                        errors += 1;
+                        warn!("Polling operation {} failed: {}", op.name, e);
+                        if errors < MAX_POLL_ERRORS {
+                            continue;
+                        }
+                        OperationStatus::failed(&format!("Polling failed: {}", e))
+                    }
+                }
+            };
+
+            if status != last_status {
+                debug!("Operation {} -> {:?}", op.name, status.state);
+                if tx
+                    .send(OperationUpdate {
+                        id,
+                        status: status.clone(),
+                    })
+                    .is_err()
+                {
+                    return;
+                }
+                last_status = status;
+            }
+
+            if last_status.is_finished() {
+                info!("Operation {} finished: {:?}", op.name, last_status.state);
+                return;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_detect_compute_operation() {
+        let response = json!({
+            "kind": "compute#operation",
+            "name": "operation-123",
+            "status": "RUNNING",
+            "selfLink": "https://www.googleapis.com/compute/v1/projects/p/zones/us-central1-a/operations/operation-123"
+        });
+        let op = detect_operation("https://compute.googleapis.com/compute/v1", &response).unwrap();
+        assert_eq!(op.kind, OperationKind::Compute);
+        assert!(op
+            .poll_url
+            .ends_with("/zones/us-central1-a/operations/operation-123"));
+    }
+
+    #[test]
+    fn test_detect_sql_and_container_operations() {
+        let sql = json!({
+            "kind": "sql#operation",
+            "name": "abc",
+            "status": "PENDING",
+            "selfLink": "https://sqladmin.googleapis.com/v1/projects/p/operations/abc"
+        });
+        let op = detect_operation("https://sqladmin.googleapis.com/v1", &sql).unwrap();
+        assert_eq!(op.kind, OperationKind::Sql);
+
+        let gke = json!({
+            "name": "operation-1",
+            "operationType": "DELETE_CLUSTER",
+            "status": "RUNNING",
+            "selfLink": "https://container.googleapis.com/v1/projects/1/locations/us-central1/operations/operation-1"
+        });
+        let op = detect_operation("https://container.googleapis.com/v1", &gke).unwrap();
+        assert_eq!(op.kind, OperationKind::Container);
+    }
+
+    #[test]
+    fn test_detect_long_running_operation() {
+        let response = json!({
+            "name": "projects/p/locations/us-central1/operations/op-1",
+            "metadata": {"@type": "type.googleapis.com/google.cloud.run.v2.Service"}
+        });
+        let op = detect_operation("https://run.googleapis.com/v2/", &response).unwrap();
+        assert_eq!(op.kind, OperationKind::LongRunning);
+        assert_eq!(
+            op.poll_url,
+            "https://run.googleapis.com/v2/projects/p/locations/us-central1/operations/op-1"
+        );
+
+        // Plain resources are not operations
+        let resource =
+            json!({"name": "projects/p/locations/us-central1/jobs/job-1", "state": "ENABLED"});
+        assert!(detect_operation("https://cloudscheduler.googleapis.com/v1", &resource).is_none());
+        assert!(detect_operation("https://x", &json!({"status": "success"})).is_none());
+    }
+
+    #[test]
+    fn test_parse_compute_status() {
+        let running = json!({"status": "RUNNING", "progress": 40});
+        let status = parse_status(OperationKind::Compute, &running);
+        assert_eq!(status.state, OperationState::Running);
+        assert_eq!(status.progress, Some(40));
+
+        let done = json!({"status": "DONE", "progress": 100});
+        assert_eq!(
+            parse_status(OperationKind::Compute, &done).state,
+            OperationState::Done
+        );
+
+        let failed = json!({
+            "status": "DONE",
+            "error": {"errors": [{"code": "QUOTA_EXCEEDED", "message": "Quota exceeded"}]}
+        });
+        let status = parse_status(OperationKind::Compute, &failed);
+        assert_eq!(status.state, OperationState::Failed);
+        assert!(status.error.unwrap().to_string().contains("QUOTA_EXCEEDED"));
+    }
+
+    #[test]
+    fn test_parse_long_running_status() {
+        let running = json!({"name": "operations/x"});
+        assert_eq!(
+            parse_status(OperationKind::LongRunning, &running).state,
+            OperationState::Running
+        );
+
+        let done = json!({"name": "operations/x", "done": true, "response": {}});
+        assert_eq!(
+            parse_status(OperationKind::LongRunning, &done).state,
+            OperationState::Done
+        );
+
+        let failed = json!({
+            "name": "operations/x",
+            "done": true,
+            "error": {"code": 9, "message": "Precondition failed"}
+        });
+        let status = parse_status(OperationKind::LongRunning, &failed);
+        assert_eq!(status.state, OperationState::Failed);
+        assert_eq!(status.error.unwrap()["code"], 9);
+    }
+}
@@ -0,0 +1,203 @@
+//! Projects, folders and organizations from Resource Manager v3
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
+use super::client::GcpClient;
+use super::dispatch::{next_page_token, with_query_param};
+
+const RESOURCE_MANAGER: &str = "https://cloudresourcemanager.googleapis.com/v3";
+
+/// Project visible to the user
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProjectInfo {
+    pub project_id: String,
+    pub display_name: String,
+    /// Project number
+    pub number: String,
+    /// Folder or organization containing the project, e.g. "folders/123"
+    pub parent: String,
+}
+
+impl ProjectInfo {
+    /// Project known only by its id (e.g., from the config file)
+    pub fn from_id(project_id: &str) -> Self {
+        Self {
+            project_id: project_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Text matched by the project picker search
+    pub fn search_text(&self) -> String {
+        format!("{} {} {}", self.project_id, self.display_name, self.number)
+    }
+}
+
+/// Folder or organization containing projects
+#[derive(Debug, Clone, PartialEq)]
+pub struct Container {
+    /// "folders/123" or "organizations/456"
+    pub name: String,
+    pub display_name: String,
+    /// Parent folder or organization, empty for organizations
+    pub parent: String,
+}
+
+impl Container {
+    pub fn is_organization(&self) -> bool {
+        self.name.starts_with("organizations/")
+    }
+}
+
+impl GcpClient {
+    /// List active projects accessible to the current user
+    pub async fn list_projects(&self) -> Result<Vec<ProjectInfo>> {
+        debug!("Listing GCP projects");
+        let items = self
+            .search_all("projects:search?query=state:ACTIVE", "projects")
+            .await?;
+        let mut projects: Vec<ProjectInfo> = items.iter().filter_map(parse_project).collect();
+        projects.sort_by(|a, b| a.project_id.cmp(&b.project_id));
+
+        info!("Found {} projects", projects.len());
+        Ok(projects)
+    }
+
+    /// List the organizations and folders the current user can see
+    /// Either list may be denied, only failing both is an error
+    pub async fn list_containers(&self) -> Result<Vec<Container>> {
+        let organizations = self
+            .search_all("organizations:search", "organizations")
+            .await;
+        let folders = self
+            .search_all("folders:search?query=state:ACTIVE", "folders")
+            .await;
+
+        let mut containers = Vec::new();
+        let mut errors = Vec::new();
+        for result in [organizations, folders] {
+            match result {
+                Ok(items) => containers.extend(items.iter().filter_map(parse_container)),
+                Err(e) => {
+                    warn!("Failed to list containers: {}", e);
+                    errors.push(e);
+                }
+            }
+        }
+        if errors.len() == 2 {
+            bail!("{}", errors.remove(1));
+        }
+
+        info!("Found {} organizations and folders", containers.len());
+        Ok(containers)
+    }
+
+    /// Follow page tokens of a Resource Manager search, up to `max_pages`
+    async fn search_all(&self, path: &str, field: &str) -> Result<Vec<Value>> {
+        let url = with_query_param(&format!("{}/{}", RESOURCE_MANAGER, path), "pageSize", "500");
+        let mut items = Vec::new();
+        let mut page_url = url.clone();
+
+        for _ in 0..self.max_pages.max(1) {
+            let response = self.request("GET", &page_url).await?;
+            if let Some(page) = response.get(field).and_then(|v| v.as_array()) {
+                items.extend(page.iter().cloned());
+            }
+            match next_page_token(&response, "nextPageToken") {
+                Some(token) => page_url = with_query_param(&url, "pageToken", &token),
+                None => return Ok(items),
+            }
+        }
+
+        warn!(
+            "Stopped listing {} after {} pages",
+            field,
+            self.max_pages.max(1)
+        );
+        Ok(items)
+    }
+}
+
+/// Project from a v3 `projects:search` item
+fn parse_project(value: &Value) -> Option<ProjectInfo> {
+    let project_id = value.get("projectId")?.as_str()?.to_string();
+
+    Some(ProjectInfo {
+        project_id,
+        display_name: text(value, "displayName"),
+        number: text(value, "name")
+            .trim_start_matches("projects/")
+            .to_string(),
+        parent: text(value, "parent"),
+    })
+}
+
+/// Folder or organization from a v3 search item
+fn parse_container(value: &Value) -> Option<Container> {
+    let name = value.get("name")?.as_str()?.to_string();
+
+    Some(Container {
+        display_name: Some(text(value, "displayName"))
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| name.clone()),
+        parent: text(value, "parent"),
+        name,
+    })
+}
+
+/// String field of an item, empty if missing
+fn text(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_project() {
+        let project = parse_project(&json!({
+            "name": "projects/123456789",
+            "projectId": "my-project",
+            "displayName": "My Project",
+            "parent": "folders/42",
+            "state": "ACTIVE"
+        }))
+        .unwrap();
+        assert_eq!(project.project_id, "my-project");
+        assert_eq!(project.display_name, "My Project");
+        assert_eq!(project.number, "123456789");
+        assert_eq!(project.parent, "folders/42");
+        assert!(project.search_text().contains("123456789"));
+
+        assert!(parse_project(&json!({"name": "projects/1"})).is_none());
+    }
+
+    #[test]
+    fn test_parse_container() {
+        let org = parse_container(&json!({
+            "name": "organizations/1",
+            "displayName": "example.com"
+        }))
+        .unwrap();
+        assert!(org.is_organization());
+        assert_eq!(org.parent, "");
+
+        let folder = parse_container(&json!({
+            "name": "folders/42",
+            "parent": "organizations/1"
+        }))
+        .unwrap();
+        assert!(!folder.is_organization());
+        // Unnamed containers show their resource name
+        assert_eq!(folder.display_name, "folders/42");
+        assert_eq!(folder.parent, "organizations/1");
+    }
+}
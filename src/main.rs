@@ -13,13 +13,22 @@ use tracing::Level;
 use tracing_subscriber::fmt::writer::MakeWriterExt;
 
 mod app;
+mod cli;
+mod clipboard;
 mod config;
+mod describe;
+mod export;
+mod filter;
+mod fuzzy;
 mod gcp;
+mod projects;
 mod resource;
 mod ui;
 
-use app::{App, Mode};
+use app::{App, Mode, YankTarget};
 use config::Config;
+use gcp::auth::TokenProvider;
+use gcp::client::ALL_ZONES;
 
 /// Version injected at compile time via TGCP_VERSION env var (set by CI/CD),
 /// or falls back to Cargo.toml version for local builds.
@@ -32,10 +41,14 @@ pub const VERSION: &str = match option_env!("TGCP_VERSION") {
 #[derive(Parser, Debug)]
 #[command(name = "tgcp", version = VERSION, about, long_about = None)]
 struct Args {
-    /// GCP zone to use (default: us-central1-a)
+    /// GCP zone to use, or "all" for zonal compute resources (default: us-central1-a)
     #[arg(short, long)]
     zone: Option<String>,
 
+    /// Region of regional resources, or "all" (default: the zone's region)
+    #[arg(long)]
+    region: Option<String>,
+
     /// Log level for debugging (logs to ~/.config/tgcp/tgcp.log)
     #[arg(long, value_enum, default_value = "off")]
     log_level: LogLevel,
@@ -43,6 +56,22 @@ struct Args {
     /// Run in read-only mode (block all write operations)
     #[arg(long)]
     readonly: bool,
+
+    /// Named context from the config file (project, location, credentials, readonly)
+    #[arg(long)]
+    context: Option<String>,
+
+    /// gcloud configuration to use as the context (default: gcloud's active one as a fallback)
+    #[arg(long, conflicts_with = "context")]
+    gcloud_config: Option<String>,
+
+    /// Service account to impersonate, or a comma-separated delegation chain ending with it
+    #[arg(long)]
+    impersonate_service_account: Option<String>,
+
+    /// Run a single command and print the result instead of starting the TUI
+    #[command(subcommand)]
+    command: Option<cli::Command>,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -125,6 +154,24 @@ async fn main() -> Result<()> {
     tracing::info!("Starting tgcp v{}", VERSION);
     tracing::debug!("CLI args: {:?}", args);
 
+    // Load config and select the context before any credential is used
+    let mut config = Config::load();
+    if let Some(name) = &args.context {
+        config.select_context(name)?;
+    }
+    if let Some(name) = &args.gcloud_config {
+        config.select_gcloud_config(name)?;
+    }
+    config.check_active_context()?;
+    config.impersonate_override = args.impersonate_service_account.clone();
+    TokenProvider::configure(config.credential_config());
+    let readonly = args.readonly || config.context_readonly();
+
+    // Headless subcommands print to stdout and never enter the TUI
+    if let Some(command) = args.command {
+        return cli::run(command, config, readonly).await;
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -142,16 +189,23 @@ async fn main() -> Result<()> {
     splash.complete_step();
     terminal.draw(|f| ui::splash::render(f, &splash))?;
 
-    // Load config
-    let config = Config::load();
     tracing::info!(
-        "Config loaded: project={:?}, zone={:?}",
+        "Config loaded: context={:?}, project={:?}, zone={:?}",
+        config.active_context,
         config.project,
         config.zone
     );
 
     // Determine effective zone: CLI arg > config > default
-    let effective_zone = args.zone.or_else(|| Some(config.effective_zone()));
+    // "all" keeps the configured zone for resources without an all-zones view
+    let all_zones = args.zone.as_deref() == Some(ALL_ZONES);
+    let effective_zone = args
+        .zone
+        .filter(|zone| zone != ALL_ZONES)
+        .unwrap_or_else(|| config.effective_zone());
+    let effective_region = args
+        .region
+        .unwrap_or_else(|| config.effective_region(&effective_zone));
     let effective_project = config.effective_project();
 
     // Create app
@@ -160,10 +214,23 @@ async fn main() -> Result<()> {
     terminal.draw(|f| ui::splash::render(f, &splash))?;
 
     tracing::info!("Creating GCP client...");
-    let mut app = match App::new(effective_zone, effective_project, config, args.readonly).await {
+    let mut app = match App::new(
+        Some(effective_zone),
+        Some(effective_region),
+        effective_project,
+        config,
+        args.readonly,
+    )
+    .await
+    {
         Ok(app) => {
             tracing::info!("GCP client created successfully");
-            tracing::info!("Project: {}, Zone: {}", app.project, app.zone);
+            tracing::info!(
+                "Project: {}, Zone: {}, Region: {}",
+                app.project,
+                app.zone,
+                app.region
+            );
             app
         }
         Err(e) => {
@@ -184,11 +251,14 @@ async fn main() -> Result<()> {
     splash.complete_step();
     terminal.draw(|f| ui::splash::render(f, &splash))?;
 
-    // Initial fetch (only if project is set)
+    if all_zones {
+        app.set_all_zones(true);
+    }
+
+    // Initial fetch (only if project is set), finishes in the background
     if app.has_project() {
         tracing::info!("Fetching initial resources...");
-        app.refresh().await;
-        tracing::info!("Initial fetch complete, {} items", app.items.len());
+        app.refresh();
     } else {
         tracing::info!("No project set, will show project selector");
     }
@@ -202,7 +272,7 @@ async fn main() -> Result<()> {
     std::thread::sleep(Duration::from_millis(200));
 
     // Main event loop
-    let result = run_app(&mut terminal, &mut app).await;
+    let result = run_app(&mut terminal, &mut app);
 
     // Restore terminal
     disable_raw_mode()?;
@@ -217,17 +287,18 @@ async fn main() -> Result<()> {
     result
 }
 
-async fn run_app(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    app: &mut App,
-) -> Result<()> {
+fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
     loop {
+        // Pick up results from background requests and operation pollers
+        app.process_task_messages();
+        app.process_operation_updates();
+
         terminal.draw(|f| ui::render(f, app))?;
 
         // Auto-refresh check
         if app.needs_refresh() {
             tracing::debug!("Auto-refresh triggered");
-            app.refresh().await;
+            app.refresh();
         }
 
         if event::poll(Duration::from_millis(100))? {
@@ -243,12 +314,12 @@ async fn run_app(
                 // Handle key events based on current mode
                 match app.mode {
                     Mode::Normal => {
-                        if handle_normal_mode(app, key.code, key.modifiers).await {
+                        if handle_normal_mode(app, key.code, key.modifiers) {
                             break;
                         }
                     }
                     Mode::Command => {
-                        if handle_command_mode(app, key.code).await {
+                        if handle_command_mode(app, key.code) {
                             break;
                         }
                     }
@@ -256,20 +327,26 @@ async fn run_app(
                         handle_help_mode(app, key.code);
                     }
                     Mode::Confirm => {
-                        handle_confirm_mode(app, key.code).await;
+                        handle_confirm_mode(app, key.code);
                     }
                     Mode::Warning => {
                         handle_warning_mode(app, key.code);
                     }
                     Mode::Projects => {
-                        handle_projects_mode(app, key.code).await;
+                        handle_projects_mode(app, key.code, key.modifiers);
                     }
                     Mode::Zones => {
-                        handle_zones_mode(app, key.code).await;
+                        handle_zones_mode(app, key.code);
                     }
                     Mode::Describe => {
                         handle_describe_mode(app, key.code);
                     }
+                    Mode::Input => {
+                        handle_input_mode(app, key.code);
+                    }
+                    Mode::Operations => {
+                        handle_operations_mode(app, key.code);
+                    }
                 }
             }
         }
@@ -278,7 +355,22 @@ async fn run_app(
     Ok(())
 }
 
-async fn handle_normal_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> bool {
+fn handle_normal_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> bool {
+    if app.filter_active {
+        handle_filter_input(app, code);
+        return false;
+    }
+    if app.yank_pending {
+        handle_yank_key(app, code);
+        return false;
+    }
+
+    // Ctrl+A marks every item passing the filter
+    if modifiers.contains(KeyModifiers::CONTROL) && code == KeyCode::Char('a') {
+        app.toggle_mark_all();
+        return false;
+    }
+
     // Check for Ctrl+D for delete/destructive action
     if modifiers.contains(KeyModifiers::CONTROL) && code == KeyCode::Char('d') {
         let shortcut = "ctrl+d".to_string();
@@ -287,7 +379,7 @@ async fn handle_normal_mode(app: &mut App, code: KeyCode, modifiers: KeyModifier
             app.trigger_action(action_index);
             // If action doesn't need confirmation, execute it
             if app.mode != Mode::Confirm {
-                app.execute_pending_action().await;
+                app.execute_pending_action();
             }
         }
         return false;
@@ -315,55 +407,35 @@ async fn handle_normal_mode(app: &mut App, code: KeyCode, modifiers: KeyModifier
         KeyCode::Home => app.go_to_top(),
         KeyCode::Char('r') => {
             tracing::debug!("Manual refresh triggered");
-            app.refresh().await;
+            app.refresh();
         }
         KeyCode::Enter | KeyCode::Char('d') => app.enter_describe_mode(),
+        KeyCode::Char(' ') => app.toggle_mark(),
+        KeyCode::Char('y') => app.yank_pending = app.selected_item().is_some(),
+        KeyCode::Char('o') => app.open_in_console(),
+        KeyCode::Char('Z') => app.toggle_all_zones(),
+        KeyCode::Char('>') => app.cycle_sort_column(true),
+        KeyCode::Char('<') => app.cycle_sort_column(false),
+        KeyCode::Char('~') => app.toggle_sort_order(),
         KeyCode::Char('?') => app.enter_help_mode(),
         KeyCode::Char(':') => app.enter_command_mode(),
-        KeyCode::Char('/') => {
-            app.filter_active = true;
-            app.filter_text.clear();
-        }
-        KeyCode::Char('0') => {
-            app.switch_zone("us-central1-a").await;
-            app.refresh().await;
-        }
-        KeyCode::Char('1') => {
-            app.switch_zone("us-east1-b").await;
-            app.refresh().await;
-        }
-        KeyCode::Char('2') => {
-            app.switch_zone("us-west1-a").await;
-            app.refresh().await;
-        }
-        KeyCode::Char('3') => {
-            app.switch_zone("europe-west1-b").await;
-            app.refresh().await;
-        }
-        KeyCode::Char('4') => {
-            app.switch_zone("asia-east1-a").await;
-            app.refresh().await;
-        }
-        KeyCode::Char('5') => {
-            app.switch_zone("asia-northeast1-a").await;
-            app.refresh().await;
+        // Reopen the bar with the current expression so it can be edited
+        KeyCode::Char('/') => app.filter_active = true,
+        KeyCode::Char(c @ '0'..='9') => {
+            app.switch_to_favorite_zone(c as usize - '0' as usize);
         }
         KeyCode::Backspace => {
-            app.navigate_back().await;
+            app.navigate_back();
         }
         KeyCode::Esc => {
             if app.filter_active || !app.filter_text.is_empty() {
                 app.clear_filter();
+            } else {
+                app.marked.clear();
             }
         }
         _ => {
-            // Handle filter input if filter is active
-            if app.filter_active {
-                if let KeyCode::Char(c) = code {
-                    app.filter_text.push(c);
-                    app.apply_filter();
-                }
-            } else if let KeyCode::Char(c) = code {
+            if let KeyCode::Char(c) = code {
                 let shortcut = c.to_string();
 
                 // First check if this is a sub-resource shortcut
@@ -373,7 +445,7 @@ async fn handle_normal_mode(app: &mut App, code: KeyCode, modifiers: KeyModifier
                         shortcut,
                         sub_resource_key
                     );
-                    app.navigate_to_sub_resource(&sub_resource_key).await;
+                    app.navigate_to_sub_resource(&sub_resource_key);
                 }
                 // Then check if this is an action shortcut
                 else if let Some(action_index) = app.find_action_by_shortcut(&shortcut) {
@@ -381,7 +453,7 @@ async fn handle_normal_mode(app: &mut App, code: KeyCode, modifiers: KeyModifier
                     app.trigger_action(action_index);
                     // If action doesn't need confirmation, execute it
                     if app.mode != Mode::Confirm {
-                        app.execute_pending_action().await;
+                        app.execute_pending_action();
                     }
                 }
             }
@@ -391,14 +463,50 @@ async fn handle_normal_mode(app: &mut App, code: KeyCode, modifiers: KeyModifier
     false
 }
 
-async fn handle_command_mode(app: &mut App, code: KeyCode) -> bool {
+/// Keys typed into the / filter bar, the list keeps filtering as you type
+fn handle_filter_input(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => app.clear_filter(),
+        // Keep the filter applied and return to list navigation
+        KeyCode::Enter => app.filter_active = false,
+        KeyCode::Backspace => {
+            app.filter_text.pop();
+            app.apply_filter();
+        }
+        KeyCode::Down => app.next(),
+        KeyCode::Up => app.previous(),
+        KeyCode::Char(c) => {
+            app.filter_text.push(c);
+            app.apply_filter();
+        }
+        _ => {}
+    }
+}
+
+/// Second key of a yank: what to copy from the selected item
+fn handle_yank_key(app: &mut App, code: KeyCode) {
+    let target = match code {
+        KeyCode::Char('y') | KeyCode::Char('n') => YankTarget::Name,
+        KeyCode::Char('i') => YankTarget::Id,
+        KeyCode::Char('l') => YankTarget::Link,
+        KeyCode::Char('j') => YankTarget::Json,
+        KeyCode::Char(c @ '1'..='9') => YankTarget::Column(c as usize - '1' as usize),
+        _ => {
+            app.yank_pending = false;
+            return;
+        }
+    };
+    app.yank(target);
+}
+
+fn handle_command_mode(app: &mut App, code: KeyCode) -> bool {
     match code {
         KeyCode::Esc => {
             app.exit_mode();
         }
         KeyCode::Enter => {
             tracing::debug!("Executing command: {}", app.command_text);
-            let should_quit = app.execute_command().await;
+            let should_quit = app.execute_command();
             if should_quit {
                 return true;
             }
@@ -435,20 +543,20 @@ fn handle_help_mode(app: &mut App, code: KeyCode) {
     }
 }
 
-async fn handle_confirm_mode(app: &mut App, code: KeyCode) {
+fn handle_confirm_mode(app: &mut App, code: KeyCode) {
     match code {
         KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
             app.exit_mode();
         }
         KeyCode::Enter => {
-            app.execute_pending_action().await;
+            app.execute_pending_action();
         }
         KeyCode::Char('y') | KeyCode::Char('Y') => {
             // Quick yes - set selected_yes and execute
             if let Some(ref mut pending) = app.pending_action {
                 pending.selected_yes = true;
             }
-            app.execute_pending_action().await;
+            app.execute_pending_action();
         }
         KeyCode::Left | KeyCode::Char('h') => {
             if let Some(ref mut pending) = app.pending_action {
@@ -465,6 +573,40 @@ async fn handle_confirm_mode(app: &mut App, code: KeyCode) {
                 pending.selected_yes = !pending.selected_yes;
             }
         }
+        // Long target lists scroll
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.scroll_dialog(true);
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.scroll_dialog(false);
+        }
+        _ => {}
+    }
+}
+
+fn handle_input_mode(app: &mut App, code: KeyCode) {
+    let Some(form) = app.action_form.as_mut() else {
+        app.exit_mode();
+        return;
+    };
+
+    match code {
+        KeyCode::Esc => {
+            app.exit_mode();
+        }
+        KeyCode::Enter => {
+            app.submit_action_form();
+            // If action doesn't need confirmation, execute it
+            if app.mode == Mode::Normal {
+                app.execute_pending_action();
+            }
+        }
+        KeyCode::Tab | KeyCode::Down => form.next_field(),
+        KeyCode::BackTab | KeyCode::Up => form.prev_field(),
+        KeyCode::Left => form.cycle_option(false),
+        KeyCode::Right => form.cycle_option(true),
+        KeyCode::Backspace => form.pop_char(),
+        KeyCode::Char(c) => form.push_char(c),
         _ => {}
     }
 }
@@ -474,44 +616,104 @@ fn handle_warning_mode(app: &mut App, code: KeyCode) {
         KeyCode::Esc | KeyCode::Enter => {
             app.exit_mode();
         }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.scroll_dialog(true);
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.scroll_dialog(false);
+        }
         _ => {}
     }
 }
 
-async fn handle_projects_mode(app: &mut App, code: KeyCode) {
+fn handle_projects_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+    // Ctrl+P pins the selected project, letters search
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        if code == KeyCode::Char('p') {
+            app.toggle_pin_project();
+        }
+        return;
+    }
     match code {
-        KeyCode::Esc => {
-            // Only allow escape if a project is already selected
-            if app.has_project() {
-                app.exit_mode();
-            }
+        // Clear the search first, then close (only if a project is already selected)
+        KeyCode::Esc if !app.project_picker.query.is_empty() => {
+            app.project_picker.query.clear();
+            app.project_picker.selected = 0;
+        }
+        KeyCode::Esc if app.has_project() => {
+            app.exit_mode();
         }
         KeyCode::Enter => {
-            app.select_project().await;
+            app.select_project();
         }
-        KeyCode::Char('j') | KeyCode::Down => {
+        KeyCode::Tab => {
+            app.toggle_project_view();
+        }
+        KeyCode::Down => {
             app.next();
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        KeyCode::Up => {
             app.previous();
         }
-        KeyCode::Char('g') => {
+        KeyCode::Home => {
             app.go_to_top();
         }
-        KeyCode::Char('G') => {
+        KeyCode::End => {
             app.go_to_bottom();
         }
+        KeyCode::Backspace => {
+            app.pop_project_query();
+        }
+        KeyCode::Char(c) => {
+            app.push_project_query(c);
+        }
         _ => {}
     }
 }
 
-async fn handle_zones_mode(app: &mut App, code: KeyCode) {
+fn handle_zones_mode(app: &mut App, code: KeyCode) {
+    // Typing goes to the search after /
+    if app.location_searching {
+        match code {
+            KeyCode::Esc => {
+                app.location_searching = false;
+                app.location_query.clear();
+                app.zones_selected = 0;
+            }
+            KeyCode::Enter => {
+                app.select_zone();
+            }
+            KeyCode::Down => {
+                app.next();
+            }
+            KeyCode::Up => {
+                app.previous();
+            }
+            KeyCode::Backspace => {
+                app.pop_location_query();
+            }
+            KeyCode::Char(c) => {
+                app.push_location_query(c);
+            }
+            _ => {}
+        }
+        return;
+    }
+
     match code {
+        // Clear the search first, then close
+        KeyCode::Esc if !app.location_query.is_empty() => {
+            app.location_query.clear();
+            app.zones_selected = 0;
+        }
         KeyCode::Esc => {
             app.exit_mode();
         }
         KeyCode::Enter => {
-            app.select_zone().await;
+            app.select_zone();
+        }
+        KeyCode::Char('/') => {
+            app.location_searching = true;
         }
         KeyCode::Char('j') | KeyCode::Down => {
             app.next();
@@ -519,33 +721,93 @@ async fn handle_zones_mode(app: &mut App, code: KeyCode) {
         KeyCode::Char('k') | KeyCode::Up => {
             app.previous();
         }
-        KeyCode::Char('g') => {
+        KeyCode::Char('g') | KeyCode::Home => {
             app.go_to_top();
         }
-        KeyCode::Char('G') => {
+        KeyCode::Char('G') | KeyCode::End => {
             app.go_to_bottom();
         }
         _ => {}
     }
 }
 
-fn handle_describe_mode(app: &mut App, code: KeyCode) {
+fn handle_operations_mode(app: &mut App, code: KeyCode) {
     match code {
-        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('d') => {
+        KeyCode::Esc | KeyCode::Char('q') => {
             app.exit_mode();
         }
+        KeyCode::Enter => {
+            app.show_selected_operation_error();
+        }
+        KeyCode::Char('c') => {
+            app.clear_finished_operations();
+        }
         KeyCode::Char('j') | KeyCode::Down => {
-            app.describe_scroll = app.describe_scroll.saturating_add(1);
+            app.next();
         }
         KeyCode::Char('k') | KeyCode::Up => {
-            app.describe_scroll = app.describe_scroll.saturating_sub(1);
+            app.previous();
         }
         KeyCode::Char('g') => {
-            app.describe_scroll = 0;
+            app.go_to_top();
         }
         KeyCode::Char('G') => {
-            app.describe_scroll_to_bottom(30); // Approximate visible lines
+            app.go_to_bottom();
+        }
+        _ => {}
+    }
+}
+
+fn handle_describe_mode(app: &mut App, code: KeyCode) {
+    let Some(data) = app.describe_data.clone() else {
+        app.exit_mode();
+        return;
+    };
+    let view = &mut app.describe_view;
+
+    // Typing a search query
+    if view.search_input {
+        match code {
+            KeyCode::Esc => view.search_input = false,
+            KeyCode::Enter => {
+                view.search_input = false;
+                let query = view.search.clone();
+                view.search(&data, &query);
+            }
+            KeyCode::Backspace => {
+                view.search.pop();
+            }
+            KeyCode::Char(c) => view.search.push(c),
+            _ => {}
         }
+        return;
+    }
+
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('d') => {
+            app.exit_mode();
+        }
+        KeyCode::Char('j') | KeyCode::Down => view.move_cursor(&data, 1),
+        KeyCode::Char('k') | KeyCode::Up => view.move_cursor(&data, -1),
+        KeyCode::PageDown => view.move_cursor(&data, view.viewport.get() as isize),
+        KeyCode::PageUp => view.move_cursor(&data, -(view.viewport.get() as isize)),
+        KeyCode::Char('g') | KeyCode::Home => view.go_to_top(),
+        KeyCode::Char('G') | KeyCode::End => view.go_to_bottom(&data),
+        KeyCode::Enter | KeyCode::Char(' ') => view.toggle_fold(&data),
+        KeyCode::Char('h') | KeyCode::Left => view.collapse(&data),
+        KeyCode::Char('l') | KeyCode::Right => view.expand(&data),
+        KeyCode::Char('c') => view.collapse_all(&data),
+        KeyCode::Char('e') => view.expand_all(&data),
+        KeyCode::Char('t') => view.toggle_format(&data),
+        KeyCode::Char('/') => {
+            view.search_input = true;
+            view.search.clear();
+        }
+        KeyCode::Char('n') => view.next_match(&data, true),
+        KeyCode::Char('N') => view.next_match(&data, false),
+        KeyCode::Char('y') => app.copy_describe_pointer(),
+        KeyCode::Char('Y') => app.copy_describe_document(),
+        KeyCode::Char('o') => app.open_in_console(),
         _ => {}
     }
 }
@@ -0,0 +1,284 @@
+//! Project picker state
+//!
+//! The picker lists projects flat (pinned, then recent, then the rest) or as
+//! a tree of organizations and folders. Typing fuzzy-searches project ids,
+//! display names and numbers in both views.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::fuzzy;
+use crate::gcp::resourcemanager::{Container, ProjectInfo};
+
+/// Containers nested deeper than this are not shown (guards against cycles)
+const MAX_TREE_DEPTH: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ProjectView {
+    #[default]
+    List,
+    Tree,
+}
+
+/// Row of the project picker
+#[derive(Debug, Clone, PartialEq)]
+pub enum PickerRow {
+    /// Index into the projects
+    Project { index: usize, depth: usize },
+    /// Index into the containers (tree view only)
+    Container {
+        index: usize,
+        depth: usize,
+        collapsed: bool,
+    },
+}
+
+#[derive(Debug, Default)]
+pub struct ProjectPicker {
+    pub view: ProjectView,
+    pub query: String,
+    /// Index into the rows
+    pub selected: usize,
+    /// Organizations and folders, fetched when the tree view first opens
+    pub containers: Vec<Container>,
+    pub containers_loaded: bool,
+    pub loading: bool,
+    /// Names of collapsed containers
+    pub collapsed: HashSet<String>,
+}
+
+impl ProjectPicker {
+    /// Rows of the current view matching the search
+    pub fn rows(
+        &self,
+        projects: &[ProjectInfo],
+        pinned: &[String],
+        recent: &[String],
+    ) -> Vec<PickerRow> {
+        match self.view {
+            ProjectView::List => self.list_rows(projects, pinned, recent),
+            ProjectView::Tree => self.tree_rows(projects),
+        }
+    }
+
+    /// Indices of projects matching the search, best match first
+    fn matching(&self, projects: &[ProjectInfo]) -> Vec<usize> {
+        let texts: Vec<(usize, String)> = projects
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, p.search_text()))
+            .collect();
+        fuzzy::filter(&self.query, &texts, |(_, text)| text)
+            .into_iter()
+            .map(|(i, _)| *i)
+            .collect()
+    }
+
+    /// Pinned projects, then recent ones, then the rest; search results by relevance
+    fn list_rows(
+        &self,
+        projects: &[ProjectInfo],
+        pinned: &[String],
+        recent: &[String],
+    ) -> Vec<PickerRow> {
+        let mut indices = self.matching(projects);
+        if self.query.trim().is_empty() {
+            let rank = |id: &str| {
+                pinned
+                    .iter()
+                    .position(|p| p == id)
+                    .or_else(|| {
+                        recent
+                            .iter()
+                            .position(|p| p == id)
+                            .map(|i| pinned.len() + i)
+                    })
+                    .unwrap_or(usize::MAX)
+            };
+            // Stable sort keeps the remaining projects in id order
+            indices.sort_by_key(|&i| rank(&projects[i].project_id));
+        }
+        indices
+            .into_iter()
+            .map(|index| PickerRow::Project { index, depth: 0 })
+            .collect()
+    }
+
+    /// Organizations and folders with their projects, searches keep the path to matches
+    fn tree_rows(&self, projects: &[ProjectInfo]) -> Vec<PickerRow> {
+        let searching = !self.query.trim().is_empty();
+        let visible: HashSet<usize> = self.matching(projects).into_iter().collect();
+        let known: HashSet<&str> = self.containers.iter().map(|c| c.name.as_str()).collect();
+
+        // Children of each container, roots under ""
+        let mut child_containers: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, container) in self.containers.iter().enumerate() {
+            let parent = if known.contains(container.parent.as_str()) {
+                container.parent.as_str()
+            } else {
+                ""
+            };
+            child_containers.entry(parent).or_default().push(i);
+        }
+        let mut child_projects: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, project) in projects.iter().enumerate() {
+            if !visible.contains(&i) {
+                continue;
+            }
+            let parent = if known.contains(project.parent.as_str()) {
+                project.parent.as_str()
+            } else {
+                ""
+            };
+            child_projects.entry(parent).or_default().push(i);
+        }
+        for children in child_containers.values_mut() {
+            children.sort_by(|&a, &b| {
+                self.containers[a]
+                    .display_name
+                    .to_lowercase()
+                    .cmp(&self.containers[b].display_name.to_lowercase())
+            });
+        }
+
+        let tree = Tree {
+            picker: self,
+            child_containers,
+            child_projects,
+            searching,
+        };
+        let mut rows = Vec::new();
+        tree.push_children("", 0, &mut rows);
+        rows
+    }
+}
+
+/// Children maps of the tree view
+struct Tree<'a> {
+    picker: &'a ProjectPicker,
+    child_containers: HashMap<&'a str, Vec<usize>>,
+    child_projects: HashMap<&'a str, Vec<usize>>,
+    searching: bool,
+}
+
+impl Tree<'_> {
+    /// Push the rows under a container, returns whether any project was pushed
+    fn push_children(&self, parent: &str, depth: usize, rows: &mut Vec<PickerRow>) -> bool {
+        if depth > MAX_TREE_DEPTH {
+            return false;
+        }
+        let mut any_project = false;
+
+        for &index in self.child_containers.get(parent).into_iter().flatten() {
+            let container = &self.picker.containers[index];
+            // Searches expand everything so matches are visible
+            let collapsed = !self.searching && self.picker.collapsed.contains(&container.name);
+
+            let row_index = rows.len();
+            rows.push(PickerRow::Container {
+                index,
+                depth,
+                collapsed,
+            });
+            let mut children = Vec::new();
+            let has_projects = self.push_children(&container.name, depth + 1, &mut children);
+            any_project |= has_projects;
+
+            if self.searching && !has_projects {
+                rows.truncate(row_index);
+            } else if !collapsed {
+                rows.extend(children);
+            }
+        }
+
+        for &index in self.child_projects.get(parent).into_iter().flatten() {
+            rows.push(PickerRow::Project { index, depth });
+            any_project = true;
+        }
+        any_project
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(id: &str, parent: &str) -> ProjectInfo {
+        ProjectInfo {
+            project_id: id.to_string(),
+            display_name: id.to_uppercase(),
+            number: String::new(),
+            parent: parent.to_string(),
+        }
+    }
+
+    fn container(name: &str, display_name: &str, parent: &str) -> Container {
+        Container {
+            name: name.to_string(),
+            display_name: display_name.to_string(),
+            parent: parent.to_string(),
+        }
+    }
+
+    fn ids(rows: &[PickerRow], projects: &[ProjectInfo]) -> Vec<String> {
+        rows.iter()
+            .map(|row| match row {
+                PickerRow::Project { index, .. } => projects[*index].project_id.clone(),
+                PickerRow::Container { index, .. } => format!("[{}]", index),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_list_rows() {
+        let projects = vec![
+            project("alpha", ""),
+            project("beta", ""),
+            project("gamma", ""),
+            project("delta", ""),
+        ];
+        let pinned = vec!["gamma".to_string()];
+        let recent = vec!["delta".to_string(), "gamma".to_string()];
+        let mut picker = ProjectPicker::default();
+
+        let rows = picker.rows(&projects, &pinned, &recent);
+        assert_eq!(ids(&rows, &projects), ["gamma", "delta", "alpha", "beta"]);
+
+        picker.query = "ta".to_string();
+        let rows = picker.rows(&projects, &pinned, &recent);
+        assert_eq!(ids(&rows, &projects), ["beta", "delta"]);
+    }
+
+    #[test]
+    fn test_tree_rows() {
+        let projects = vec![
+            project("web", "folders/2"),
+            project("api", "folders/3"),
+            project("loose", ""),
+        ];
+        let mut picker = ProjectPicker {
+            view: ProjectView::Tree,
+            containers: vec![
+                container("organizations/1", "example.com", ""),
+                container("folders/3", "Prod", "organizations/1"),
+                container("folders/2", "Dev", "organizations/1"),
+            ],
+            ..Default::default()
+        };
+
+        let rows = picker.rows(&projects, &[], &[]);
+        assert_eq!(
+            ids(&rows, &projects),
+            ["[0]", "[2]", "web", "[1]", "api", "loose"]
+        );
+        assert_eq!(rows[2], PickerRow::Project { index: 0, depth: 2 });
+
+        picker.collapsed.insert("folders/2".to_string());
+        let rows = picker.rows(&projects, &[], &[]);
+        assert_eq!(ids(&rows, &projects), ["[0]", "[2]", "[1]", "api", "loose"]);
+
+        // Searches drop folders without matches and expand the rest
+        picker.query = "web".to_string();
+        let rows = picker.rows(&projects, &[], &[]);
+        assert_eq!(ids(&rows, &projects), ["[0]", "[2]", "web"]);
+    }
+}
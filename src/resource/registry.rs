@@ -1,8 +1,13 @@
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{Map, Value};
 use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
+use crate::config::Config;
+
 const RESOURCE_FILES: &[&str] = &[
     include_str!("../resources/common.json"),
     include_str!("../resources/compute.json"),
@@ -43,6 +48,135 @@ pub struct ApiDef {
     pub base: String,
     pub path: String,
     pub method: String,
+    /// Server-side filtering accepted by the list endpoint
+    #[serde(default)]
+    pub filter: Option<FilterDef>,
+    /// The list endpoint accepts `-` for `{region}` to list every region at once,
+    /// otherwise listing all regions sends one request per region
+    #[serde(default)]
+    pub region_wildcard: bool,
+    /// List endpoint covering every zone, used in all-zones mode
+    #[serde(default)]
+    pub aggregated: Option<AggregatedDef>,
+}
+
+/// Aggregated list of a zonal resource (e.g., `aggregated/instances`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct AggregatedDef {
+    /// Path relative to `api.base`
+    pub path: String,
+    /// Items path in the response, `items.*.<field>` for per-zone maps
+    pub response_path: String,
+}
+
+/// Expression language of a server-side filter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterSyntax {
+    /// AIP-160 style expressions used by most newer APIs
+    #[default]
+    Aip160,
+    /// Compute Engine list filters
+    Compute,
+    /// One of a fixed set of values (`options`)
+    Enum,
+}
+
+impl FilterSyntax {
+    /// Example expression shown as a hint
+    pub fn example(self) -> &'static str {
+        match self {
+            FilterSyntax::Aip160 => "status=\"SUCCESS\"",
+            FilterSyntax::Compute => "status = RUNNING AND labels.env = prod",
+            FilterSyntax::Enum => "",
+        }
+    }
+}
+
+/// Server-side filter of a list endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct FilterDef {
+    /// Query parameter carrying the expression
+    #[serde(default = "default_filter_param")]
+    pub param: String,
+    #[serde(default)]
+    pub syntax: FilterSyntax,
+    /// Expression sent when none is set
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Accepted values for the `enum` syntax
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+fn default_filter_param() -> String {
+    "filter".to_string()
+}
+
+impl FilterDef {
+    /// Check an expression before it is sent, normalising enum values
+    pub fn validate(&self, expr: &str) -> Result<String, String> {
+        let expr = expr.trim();
+        if self.syntax != FilterSyntax::Enum {
+            return Ok(expr.to_string());
+        }
+        self.options
+            .iter()
+            .find(|o| o.eq_ignore_ascii_case(expr))
+            .cloned()
+            .ok_or_else(|| format!("expected one of {}", self.options.join(", ")))
+    }
+
+    /// Hint listing the accepted values or an example expression
+    pub fn hint(&self) -> String {
+        match self.syntax {
+            FilterSyntax::Enum => self.options.join(" | "),
+            syntax => format!("e.g. {}", syntax.example()),
+        }
+    }
+}
+
+/// How a list endpoint splits results across pages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaginationStyle {
+    /// Follow a continuation token until the response no longer returns one
+    #[default]
+    Token,
+    /// Single request, the endpoint does not paginate
+    None,
+}
+
+/// Pagination settings for a list endpoint
+/// Defaults match the standard GCP `pageToken`/`nextPageToken` convention
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaginationDef {
+    #[serde(default)]
+    pub style: PaginationStyle,
+    /// Query parameter used to request the next page
+    #[serde(default = "default_token_param")]
+    pub token_param: String,
+    /// Response field (dot notation) holding the next page token
+    #[serde(default = "default_token_field")]
+    pub token_field: String,
+}
+
+fn default_token_param() -> String {
+    "pageToken".to_string()
+}
+
+fn default_token_field() -> String {
+    "nextPageToken".to_string()
+}
+
+impl Default for PaginationDef {
+    fn default() -> Self {
+        Self {
+            style: PaginationStyle::default(),
+            token_param: default_token_param(),
+            token_field: default_token_field(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -67,6 +201,111 @@ pub struct ActionApiDef {
     pub path: String,
 }
 
+/// Type of an action input parameter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamType {
+    #[default]
+    String,
+    Integer,
+    /// RFC3339 timestamp (e.g., 2024-01-31T12:00:00Z)
+    Timestamp,
+    /// One of the values listed in `options`
+    Enum,
+}
+
+/// Input parameter prompted for before an action is dispatched
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParamDef {
+    /// Placeholder name used in the body template and path (e.g., "sizeGb")
+    pub name: String,
+    /// Label shown in the input form
+    pub label: String,
+    #[serde(rename = "type", default)]
+    pub param_type: ParamType,
+    /// Default value, may reference fields of the selected item (e.g., "{sizeGb}")
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Allowed values for enum parameters
+    #[serde(default)]
+    pub options: Vec<String>,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+impl ParamDef {
+    /// Initial value for this parameter: its default with `{field}` references
+    /// resolved against the target item, or the first option for enums
+    pub fn initial_value(&self, item: &Value) -> String {
+        if let Some(default) = &self.default {
+            let mut value = String::new();
+            let mut rest = default.as_str();
+            while let Some(start) = rest.find('{') {
+                let Some(len) = rest[start..].find('}') else {
+                    break;
+                };
+                value.push_str(&rest[..start]);
+                let resolved = extract_json_value(item, &rest[start + 1..start + len]);
+                if resolved != "-" {
+                    value.push_str(&resolved);
+                }
+                rest = &rest[start + len + 1..];
+            }
+            value.push_str(rest);
+            return value;
+        }
+
+        if self.param_type == ParamType::Enum {
+            return self.options.first().cloned().unwrap_or_default();
+        }
+
+        String::new()
+    }
+
+    /// Validate raw input and convert it to the JSON value sent in the request body
+    pub fn parse_value(&self, input: &str) -> Result<Value, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return if self.required {
+                Err(format!("{} is required", self.label))
+            } else {
+                Ok(Value::Null)
+            };
+        }
+
+        match self.param_type {
+            ParamType::String => Ok(Value::String(input.to_string())),
+            ParamType::Integer => input
+                .parse::<i64>()
+                .map(Value::from)
+                .map_err(|_| format!("{} must be a whole number", self.label)),
+            ParamType::Timestamp => chrono::DateTime::parse_from_rfc3339(input)
+                .map(|_| Value::String(input.to_string()))
+                .map_err(|_| {
+                    format!(
+                        "{} must be an RFC3339 timestamp (e.g., 2024-01-31T12:00:00Z)",
+                        self.label
+                    )
+                }),
+            ParamType::Enum => {
+                if self.options.iter().any(|o| o == input) {
+                    Ok(Value::String(input.to_string()))
+                } else {
+                    Err(format!(
+                        "{} must be one of: {}",
+                        self.label,
+                        self.options.join(", ")
+                    ))
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ActionDef {
     pub display_name: String,
@@ -75,6 +314,29 @@ pub struct ActionDef {
     pub shortcut: Option<String>,
     #[serde(default)]
     pub confirm: Option<ConfirmConfig>,
+    /// JSON body template, "{param}" strings are replaced with parameter values
+    #[serde(default)]
+    pub body: Option<Value>,
+    /// Parameters collected from the user before dispatch
+    #[serde(default)]
+    pub params: Vec<ParamDef>,
+}
+
+/// API fetching a single resource for the describe view
+/// The path is relative to `api.base`; `{field}` placeholders not provided by
+/// the context are read from the list item (dot notation is supported)
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetApiDef {
+    pub path: String,
+}
+
+/// Initial sort order of the resource table
+#[derive(Debug, Clone, Deserialize)]
+pub struct SortDef {
+    /// Column header (case-insensitive) or json_path
+    pub column: String,
+    #[serde(default)]
+    pub descending: bool,
 }
 
 /// Sub-resource definition from JSON
@@ -96,15 +358,40 @@ pub struct ResourceDef {
     pub service: String,
     pub api: ApiDef,
     pub response_path: String,
+    #[serde(default)]
+    pub pagination: PaginationDef,
+    /// Optional API returning the full resource (list APIs often omit fields)
+    #[serde(default)]
+    pub get: Option<GetApiDef>,
     pub id_field: String,
     pub name_field: String,
+    /// Cloud Console page of an item, placeholders as in `get.path` plus
+    /// `{short_name}` and `{location}` taken from a full resource name
+    #[serde(default)]
+    pub console_url: Option<String>,
     pub columns: Vec<ColumnDef>,
     #[serde(default)]
+    pub default_sort: Option<SortDef>,
+    #[serde(default)]
     pub actions: Vec<ActionDef>,
     #[serde(default)]
     pub sub_resources: Vec<SubResourceDef>,
 }
 
+impl ResourceDef {
+    /// Index of the column matching a header (case-insensitive) or json_path
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|c| c.header.eq_ignore_ascii_case(name) || c.json_path == name)
+    }
+
+    /// Listed per region, through a `{region}` placeholder in the list path
+    pub fn is_regional(&self) -> bool {
+        self.api.path.contains("{region}")
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ColorDef {
     pub value: String,
@@ -117,32 +404,345 @@ pub struct ResourceConfig {
     pub color_maps: HashMap<String, Vec<ColorDef>>,
     #[serde(default)]
     pub resources: HashMap<String, ResourceDef>,
+    /// Problems found while loading definitions, reported at startup
+    #[serde(skip)]
+    pub issues: Vec<RegistryIssue>,
+}
+
+/// Problem found in a resource definition file
+#[derive(Debug, Clone)]
+pub struct RegistryIssue {
+    /// File the problem was found in ("built-in" for embedded definitions)
+    pub source: String,
+    pub message: String,
+}
+
+impl RegistryIssue {
+    fn new(source: &str, message: impl Into<String>) -> Self {
+        Self {
+            source: source.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for RegistryIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.source, self.message)
+    }
 }
 
 static REGISTRY: OnceLock<ResourceConfig> = OnceLock::new();
 
+const BUILTIN_SOURCE: &str = "built-in";
+
+/// Get the resource registry: embedded definitions merged with user definitions
+/// from the resources directory (see `user_resources_dir`)
 pub fn get_registry() -> &'static ResourceConfig {
     REGISTRY.get_or_init(|| {
-        let mut final_config = ResourceConfig {
-            color_maps: HashMap::new(),
-            resources: HashMap::new(),
+        let (user_files, mut issues) = read_user_files(&user_resources_dir());
+        let mut config = build_registry(RESOURCE_FILES, &user_files);
+        issues.append(&mut config.issues);
+        for issue in &issues {
+            tracing::warn!("Resource definition problem: {}", issue);
+        }
+        config.issues = issues;
+        config
+    })
+}
+
+/// Summary of registry problems for display, None if everything loaded
+/// Lists at most `limit` issues
+pub fn registry_report(limit: usize) -> Option<String> {
+    let issues = &get_registry().issues;
+    if issues.is_empty() {
+        return None;
+    }
+
+    let mut report = format!(
+        "{} problem(s) in resource definitions, affected entries were skipped:",
+        issues.len()
+    );
+    for issue in issues.iter().take(limit) {
+        report.push('\n');
+        report.push_str(&issue.to_string());
+    }
+    if issues.len() > limit {
+        report.push_str(&format!("\n...and {} more (see log)", issues.len() - limit));
+    }
+    Some(report)
+}
+
+/// Directory holding user resource definitions (e.g., ~/.config/tgcp/resources)
+pub fn user_resources_dir() -> PathBuf {
+    Config::config_dir().join("resources")
+}
+
+/// Read every JSON/YAML file in the user resources directory, sorted by name
+/// A missing directory is not an error
+fn read_user_files(dir: &Path) -> (Vec<(String, Value)>, Vec<RegistryIssue>) {
+    let mut files = Vec::new();
+    let mut issues = Vec::new();
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (files, issues);
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            matches!(
+                p.extension().and_then(|e| e.to_str()),
+                Some("json" | "yaml" | "yml")
+            )
+        })
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let source = path.display().to_string();
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                issues.push(RegistryIssue::new(&source, e.to_string()));
+                continue;
+            }
         };
+        let parsed = if path.extension().is_some_and(|e| e == "json") {
+            serde_json::from_str::<Value>(&content).map_err(|e| e.to_string())
+        } else {
+            serde_yaml::from_str::<Value>(&content).map_err(|e| e.to_string())
+        };
+        match parsed {
+            Ok(value) => {
+                tracing::info!("Loaded user resource definitions from {}", source);
+                files.push((source, value));
+            }
+            Err(e) => issues.push(RegistryIssue::new(&source, e)),
+        }
+    }
+
+    (files, issues)
+}
 
-        for content in RESOURCE_FILES {
-            let partial: ResourceConfig =
-                serde_json::from_str(content).expect("Failed to parse embedded resource JSON");
-            final_config.color_maps.extend(partial.color_maps);
-            final_config.resources.extend(partial.resources);
+/// Merge embedded and user definitions into a registry
+///
+/// User files are applied in order on top of the embedded ones. A color map
+/// replaces the one with the same name. A resource with a new key is added; for an
+/// existing key, every top-level field given (e.g., `columns`, `actions`) replaces
+/// the built-in field. Each file's result is validated on its own: an entry that
+/// doesn't validate is reported in `issues` and the previous definition is kept.
+fn build_registry(builtin: &[&str], user_files: &[(String, Value)]) -> ResourceConfig {
+    let mut issues = Vec::new();
+    let mut color_maps: Map<String, Value> = Map::new();
+    // Resource key -> (source of the last change, merged definition)
+    let mut resources: HashMap<String, (String, Value)> = HashMap::new();
+
+    for content in builtin {
+        match serde_json::from_str::<Value>(content) {
+            Ok(value) => merge_file(
+                BUILTIN_SOURCE,
+                value,
+                &mut color_maps,
+                &mut resources,
+                &mut issues,
+            ),
+            Err(e) => issues.push(RegistryIssue::new(BUILTIN_SOURCE, e.to_string())),
         }
+    }
+    for (source, value) in user_files {
+        merge_file(
+            source,
+            value.clone(),
+            &mut color_maps,
+            &mut resources,
+            &mut issues,
+        );
+    }
 
-        final_config
-    })
+    let mut config = ResourceConfig {
+        color_maps: HashMap::new(),
+        resources: HashMap::new(),
+        issues: Vec::new(),
+    };
+
+    for (name, map) in color_maps {
+        match serde_json::from_value::<Vec<ColorDef>>(map) {
+            Ok(map) => {
+                config.color_maps.insert(name, map);
+            }
+            Err(e) => issues.push(RegistryIssue::new(
+                "color_maps",
+                format!("color map '{}': {}", name, e),
+            )),
+        }
+    }
+
+    let mut sources = HashMap::new();
+    for (key, (source, def)) in resources {
+        match serde_json::from_value::<ResourceDef>(def) {
+            Ok(def) => {
+                config.resources.insert(key.clone(), def);
+                sources.insert(key, source);
+            }
+            Err(e) => issues.push(RegistryIssue::new(
+                &source,
+                format!("resource '{}': {}", key, e),
+            )),
+        }
+    }
+
+    // Cross-references are only checked once everything is merged
+    for (key, def) in &config.resources {
+        let source = &sources[key];
+        for col in &def.columns {
+            if let Some(map) = &col.color_map {
+                if !config.color_maps.contains_key(map) {
+                    issues.push(RegistryIssue::new(
+                        source,
+                        format!(
+                            "resource '{}': column '{}' uses unknown color map '{}'",
+                            key, col.header, map
+                        ),
+                    ));
+                }
+            }
+        }
+        if let Some(sort) = &def.default_sort {
+            if def.column_index(&sort.column).is_none() {
+                issues.push(RegistryIssue::new(
+                    source,
+                    format!(
+                        "resource '{}': default sort uses unknown column '{}'",
+                        key, sort.column
+                    ),
+                ));
+            }
+        }
+        for sub in &def.sub_resources {
+            if !config.resources.contains_key(&sub.resource_key) {
+                issues.push(RegistryIssue::new(
+                    source,
+                    format!(
+                        "resource '{}': unknown sub-resource '{}'",
+                        key, sub.resource_key
+                    ),
+                ));
+            }
+        }
+    }
+
+    issues.sort_by(|a, b| (&a.source, &a.message).cmp(&(&b.source, &b.message)));
+    config.issues = issues;
+    config
+}
+
+/// Merge one definition file into the collected color maps and resources
+fn merge_file(
+    source: &str,
+    value: Value,
+    color_maps: &mut Map<String, Value>,
+    resources: &mut HashMap<String, (String, Value)>,
+    issues: &mut Vec<RegistryIssue>,
+) {
+    let Value::Object(mut file) = value else {
+        issues.push(RegistryIssue::new(
+            source,
+            "expected an object with `resources` and/or `color_maps`",
+        ));
+        return;
+    };
+
+    match file.remove("color_maps") {
+        Some(Value::Object(maps)) => {
+            for (name, map) in maps {
+                match serde_json::from_value::<Vec<ColorDef>>(map.clone()) {
+                    Ok(_) => {
+                        color_maps.insert(name, map);
+                    }
+                    Err(e) => issues.push(RegistryIssue::new(
+                        source,
+                        format!("color map '{}': {}", name, e),
+                    )),
+                }
+            }
+        }
+        Some(_) => issues.push(RegistryIssue::new(source, "`color_maps` must be an object")),
+        None => {}
+    }
+
+    let defs = match file.remove("resources") {
+        Some(Value::Object(defs)) => defs,
+        Some(_) => {
+            issues.push(RegistryIssue::new(source, "`resources` must be an object"));
+            return;
+        }
+        None => return,
+    };
+
+    for (key, def) in defs {
+        let Value::Object(fields) = def else {
+            issues.push(RegistryIssue::new(
+                source,
+                format!("resource '{}' must be an object", key),
+            ));
+            continue;
+        };
+
+        let mut merged = match resources.get(&key) {
+            Some((_, Value::Object(existing))) => existing.clone(),
+            _ => Map::new(),
+        };
+        merged.extend(fields);
+        let merged = Value::Object(merged);
+
+        // A bad override must not take the built-in definition down with it
+        if let Err(e) = serde_json::from_value::<ResourceDef>(merged.clone()) {
+            issues.push(RegistryIssue::new(
+                source,
+                format!("resource '{}': {}", key, e),
+            ));
+            continue;
+        }
+        resources.insert(key, (source.to_string(), merged));
+    }
 }
 
 pub fn get_resource(key: &str) -> Option<&'static ResourceDef> {
     get_registry().resources.get(key)
 }
 
+static ALL_REGIONS_RESOURCES: OnceLock<HashMap<String, ResourceDef>> = OnceLock::new();
+
+/// Definition of a regional resource listed across all regions: the same
+/// resource with a Location column appended, unless it already has one
+pub fn get_all_regions_resource(key: &str) -> Option<&'static ResourceDef> {
+    ALL_REGIONS_RESOURCES
+        .get_or_init(|| {
+            get_registry()
+                .resources
+                .iter()
+                .filter(|(_, r)| r.is_regional())
+                .map(|(key, r)| (key.clone(), with_location_column(r)))
+                .collect()
+        })
+        .get(key)
+}
+
+fn with_location_column(resource: &ResourceDef) -> ResourceDef {
+    let mut resource = resource.clone();
+    if resource.column_index("location").is_none() {
+        // Appended so the indices of sort and yank columns stay the same
+        resource.columns.push(ColumnDef {
+            header: "Location".to_string(),
+            json_path: "location".to_string(),
+            width: 12,
+            color_map: None,
+        });
+    }
+    resource
+}
+
 pub fn get_all_resource_keys() -> Vec<&'static str> {
     get_registry()
         .resources
@@ -227,7 +827,7 @@ mod tests {
 
     #[test]
     fn test_registry_loads_successfully() {
-        let registry = get_registry();
+        let registry = build_registry(RESOURCE_FILES, &[]);
         assert!(
             !registry.resources.is_empty(),
             "Registry should have resources"
@@ -356,7 +956,7 @@ mod tests {
 
     #[test]
     fn test_all_resources_have_required_fields() {
-        let registry = get_registry();
+        let registry = build_registry(RESOURCE_FILES, &[]);
 
         for (key, resource) in &registry.resources {
             assert!(
@@ -394,7 +994,7 @@ mod tests {
 
     #[test]
     fn test_all_columns_have_required_fields() {
-        let registry = get_registry();
+        let registry = build_registry(RESOURCE_FILES, &[]);
 
         for (key, resource) in &registry.resources {
             for (i, col) in resource.columns.iter().enumerate() {
@@ -422,7 +1022,7 @@ mod tests {
 
     #[test]
     fn test_actions_have_required_fields() {
-        let registry = get_registry();
+        let registry = build_registry(RESOURCE_FILES, &[]);
 
         for (key, resource) in &registry.resources {
             for action in &resource.actions {
@@ -449,7 +1049,7 @@ mod tests {
 
     #[test]
     fn test_sub_resources_have_required_fields() {
-        let registry = get_registry();
+        let registry = build_registry(RESOURCE_FILES, &[]);
 
         for (key, resource) in &registry.resources {
             for sub in &resource.sub_resources {
@@ -484,9 +1084,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_action_shortcuts_are_not_reserved() {
+        // Keys the list view handles before action shortcuts, see CONTRIBUTING.md
+        const RESERVED: &[&str] = &[
+            "d", "g", "G", "j", "k", "r", "q", "?", ":", "/", "<", ">", "~", " ", "ctrl+a", "y",
+            "o", "Z", "0", "1", "2", "3", "4", "5", "6", "7", "8", "9",
+        ];
+        let registry = build_registry(RESOURCE_FILES, &[]);
+
+        for (key, resource) in &registry.resources {
+            for action in &resource.actions {
+                let Some(shortcut) = &action.shortcut else {
+                    continue;
+                };
+                assert!(
+                    !RESERVED.contains(&shortcut.as_str()),
+                    "Resource '{}' action '{}' uses the reserved shortcut '{}'",
+                    key,
+                    action.display_name,
+                    shortcut
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_destructive_actions_have_confirmation() {
-        let registry = get_registry();
+        let registry = build_registry(RESOURCE_FILES, &[]);
 
         for (key, resource) in &registry.resources {
             for action in &resource.actions {
@@ -510,9 +1135,214 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_action_params_are_valid() {
+        let registry = build_registry(RESOURCE_FILES, &[]);
+
+        for (key, resource) in &registry.resources {
+            for action in &resource.actions {
+                for param in &action.params {
+                    assert!(
+                        !param.name.is_empty() && !param.label.is_empty(),
+                        "Resource '{}' action '{}' has a param without name or label",
+                        key,
+                        action.display_name
+                    );
+                    if param.param_type == ParamType::Enum {
+                        assert!(
+                            !param.options.is_empty(),
+                            "Resource '{}' action '{}' enum param '{}' should have options",
+                            key,
+                            action.display_name,
+                            param.name
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_disk_resize_has_body_and_param() {
+        let disks = get_resource("disks").unwrap();
+        let resize = disks
+            .actions
+            .iter()
+            .find(|a| a.display_name == "Resize")
+            .unwrap();
+        assert!(resize.body.is_some(), "Resize should send a body");
+        assert_eq!(resize.params.len(), 1);
+        assert_eq!(resize.params[0].name, "sizeGb");
+        assert_eq!(resize.params[0].param_type, ParamType::Integer);
+    }
+
+    #[test]
+    fn test_all_regions_resource() {
+        let services = get_all_regions_resource("cloudrun-services").unwrap();
+        let base = get_resource("cloudrun-services").unwrap();
+        assert_eq!(services.columns.len(), base.columns.len() + 1);
+        assert_eq!(services.columns.last().unwrap().json_path, "location");
+
+        // Only regional resources have a variant
+        assert!(get_all_regions_resource("vm-instances").is_none());
+        assert!(get_all_regions_resource("gke-clusters").is_none());
+    }
+
+    #[test]
+    fn test_server_filter_definitions() {
+        let vms = get_resource("vm-instances").unwrap();
+        let filter = vms
+            .api
+            .filter
+            .as_ref()
+            .expect("compute lists support filter");
+        assert_eq!(filter.param, "filter");
+        assert_eq!(filter.syntax, FilterSyntax::Compute);
+        assert_eq!(
+            filter.validate(" status = RUNNING "),
+            Ok("status = RUNNING".to_string())
+        );
+
+        // Dataflow takes one of a fixed set of values instead of an expression
+        let jobs = get_resource("dataflow-jobs").unwrap();
+        assert!(!jobs.api.path.contains('?'));
+        let filter = jobs.api.filter.as_ref().unwrap();
+        assert_eq!(filter.default.as_deref(), Some("ACTIVE"));
+        assert_eq!(filter.validate("terminated"), Ok("TERMINATED".to_string()));
+        assert!(filter.validate("RUNNING").is_err());
+    }
+
+    #[test]
+    fn test_param_parse_value() {
+        let param = |param_type, options: &[&str]| ParamDef {
+            name: "p".to_string(),
+            label: "P".to_string(),
+            param_type,
+            default: None,
+            options: options.iter().map(|s| s.to_string()).collect(),
+            required: true,
+        };
+
+        let int = param(ParamType::Integer, &[]);
+        assert_eq!(int.parse_value(" 200 "), Ok(serde_json::json!(200)));
+        assert!(int.parse_value("20GB").is_err());
+        assert!(int.parse_value("").is_err());
+
+        let ts = param(ParamType::Timestamp, &[]);
+        assert!(ts.parse_value("2024-01-31T12:00:00Z").is_ok());
+        assert!(ts.parse_value("2024-01-31").is_err());
+
+        let choice = param(ParamType::Enum, &["ACTIVE", "DEPRECATED"]);
+        assert!(choice.parse_value("DEPRECATED").is_ok());
+        assert!(choice.parse_value("deprecated").is_err());
+
+        let mut optional = param(ParamType::String, &[]);
+        optional.required = false;
+        assert_eq!(optional.parse_value(""), Ok(Value::Null));
+    }
+
+    #[test]
+    fn test_builtin_definitions_have_no_issues() {
+        let config = build_registry(RESOURCE_FILES, &[]);
+        let issues: Vec<String> = config.issues.iter().map(|i| i.to_string()).collect();
+        assert!(issues.is_empty(), "Built-in definitions: {:?}", issues);
+    }
+
+    #[test]
+    fn test_user_definitions_merge() {
+        let user = serde_json::json!({
+            "color_maps": {
+                "status": [{"value": "RUNNING", "color": [1, 2, 3]}]
+            },
+            "resources": {
+                "vm-instances": {
+                    "columns": [{"header": "Name", "json_path": "name", "width": 100}]
+                },
+                "batch-jobs": {
+                    "display_name": "Batch Jobs",
+                    "service": "batch",
+                    "api": {
+                        "base": "https://batch.googleapis.com/v1",
+                        "path": "projects/{project}/locations/{region}/jobs",
+                        "method": "GET"
+                    },
+                    "response_path": "jobs",
+                    "id_field": "uid",
+                    "name_field": "name",
+                    "columns": [{"header": "Name", "json_path": "name", "width": 100}]
+                }
+            }
+        });
+        let config = build_registry(RESOURCE_FILES, &[("user.yaml".to_string(), user)]);
+        assert!(config.issues.is_empty(), "{:?}", config.issues);
+
+        // Overridden columns, everything else kept from the built-in definition
+        let vm = &config.resources["vm-instances"];
+        assert_eq!(vm.columns.len(), 1);
+        assert_eq!(vm.display_name, "VM Instances");
+        assert!(!vm.actions.is_empty());
+
+        assert!(config.resources.contains_key("batch-jobs"));
+        assert_eq!(config.color_maps["status"].len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_user_definitions_are_reported() {
+        let user = serde_json::json!({
+            "resources": {
+                "broken": {"display_name": "Broken"},
+                "vm-instances": {
+                    "columns": [{"header": "State", "json_path": "status", "width": 100, "color_map": "nope"}]
+                }
+            }
+        });
+        let config = build_registry(RESOURCE_FILES, &[("user.json".to_string(), user)]);
+
+        // The broken resource is skipped, the rest of the registry still loads
+        assert!(!config.resources.contains_key("broken"));
+        assert!(config.resources.contains_key("buckets"));
+
+        assert_eq!(config.issues.len(), 2);
+        assert!(config.issues.iter().all(|i| i.source == "user.json"));
+        assert!(config.issues[0].message.contains("resource 'broken'"));
+        assert!(config.issues[1]
+            .message
+            .contains("unknown color map 'nope'"));
+
+        let config = build_registry(&["{not json"], &[]);
+        assert_eq!(config.issues.len(), 1);
+        assert_eq!(config.issues[0].source, "built-in");
+    }
+
+    #[test]
+    fn test_invalid_override_keeps_previous_definition() {
+        let bad = serde_json::json!({
+            "color_maps": {"status": "green"},
+            "resources": {"vm-instances": {"columns": "Name"}}
+        });
+        let good = serde_json::json!({
+            "resources": {"vm-instances": {"display_name": "VMs"}}
+        });
+        let config = build_registry(
+            RESOURCE_FILES,
+            &[
+                ("bad.yaml".to_string(), bad),
+                ("good.yaml".to_string(), good),
+            ],
+        );
+
+        // The bad file is reported, the built-in definition and color map survive
+        assert_eq!(config.issues.len(), 2, "{:?}", config.issues);
+        assert!(config.issues.iter().all(|i| i.source == "bad.yaml"));
+        let vm = &config.resources["vm-instances"];
+        assert_eq!(vm.display_name, "VMs");
+        assert!(vm.columns.len() > 1);
+        assert!(config.color_maps["status"].len() > 1);
+    }
+
     #[test]
     fn test_color_maps_exist() {
-        let registry = get_registry();
+        let registry = build_registry(RESOURCE_FILES, &[]);
 
         // Check that status color map exists (used by multiple resources)
         assert!(
@@ -0,0 +1,201 @@
+//! Type-aware ordering of resource table rows
+//!
+//! Cells are compared by what they contain rather than as plain text: numbers
+//! and sizes by magnitude, RFC3339 timestamps chronologically and status
+//! columns by their position in the column's color map.
+
+use super::registry::{extract_json_value, get_color_map, ColumnDef};
+use chrono::{DateTime, FixedOffset};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// Active sort of the resource table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SortState {
+    /// Index into the resource columns
+    pub column: usize,
+    pub descending: bool,
+}
+
+/// Comparable form of a cell value
+#[derive(Debug, PartialEq)]
+enum SortKey {
+    /// Position in the column's color map
+    Rank(usize),
+    Number(f64),
+    Time(DateTime<FixedOffset>),
+    Text(String),
+}
+
+impl SortKey {
+    fn parse(value: &str, order: Option<&[String]>) -> Self {
+        if let Some(rank) = order.and_then(|o| o.iter().position(|v| v == value)) {
+            return SortKey::Rank(rank);
+        }
+        if let Ok(n) = value.parse::<f64>() {
+            return SortKey::Number(n);
+        }
+        if let Some(bytes) = parse_size(value) {
+            return SortKey::Number(bytes);
+        }
+        if let Ok(t) = DateTime::parse_from_rfc3339(value) {
+            return SortKey::Time(t);
+        }
+        SortKey::Text(value.to_lowercase())
+    }
+
+    /// Kinds are ordered among themselves when a column mixes them
+    fn kind(&self) -> u8 {
+        match self {
+            SortKey::Rank(_) => 0,
+            SortKey::Number(_) => 1,
+            SortKey::Time(_) => 2,
+            SortKey::Text(_) => 3,
+        }
+    }
+
+    fn compare(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortKey::Rank(a), SortKey::Rank(b)) => a.cmp(b),
+            (SortKey::Number(a), SortKey::Number(b)) => a.total_cmp(b),
+            (SortKey::Time(a), SortKey::Time(b)) => a.cmp(b),
+            (SortKey::Text(a), SortKey::Text(b)) => a.cmp(b),
+            _ => self.kind().cmp(&other.kind()),
+        }
+    }
+}
+
+/// Parse a human-readable size such as "10GB", "1.5 GiB" or "512M" into bytes
+fn parse_size(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let split = value.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+
+    let unit = unit.trim().to_ascii_uppercase();
+    let (prefix, base) = match unit.strip_suffix("IB") {
+        Some(prefix) => (prefix, 1024f64),
+        None => (unit.strip_suffix('B').unwrap_or(&unit), 1000f64),
+    };
+    let exponent = match prefix {
+        "" => 0,
+        "K" => 1,
+        "M" => 2,
+        "G" => 3,
+        "T" => 4,
+        "P" => 5,
+        _ => return None,
+    };
+    Some(number * base.powi(exponent))
+}
+
+/// Sort items by a column; items without a value stay at the end in both directions
+pub fn sort_items(items: &mut [Value], column: &ColumnDef, descending: bool) {
+    let order: Option<Vec<String>> = column
+        .color_map
+        .as_deref()
+        .and_then(get_color_map)
+        .map(|map| map.iter().map(|c| c.value.clone()).collect());
+
+    // Parse each cell once instead of on every comparison
+    let mut keyed: Vec<(Option<SortKey>, Value)> = items
+        .iter()
+        .map(|item| {
+            let value = extract_json_value(item, &column.json_path);
+            let key = (value != "-").then(|| SortKey::parse(&value, order.as_deref()));
+            (key, item.clone())
+        })
+        .collect();
+
+    // Stable sort keeps the API order between equal values
+    keyed.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) if descending => b.compare(a),
+        (Some(a), Some(b)) => a.compare(b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+
+    for (slot, (_, item)) in items.iter_mut().zip(keyed) {
+        *slot = item;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn column(json_path: &str, color_map: Option<&str>) -> ColumnDef {
+        ColumnDef {
+            header: json_path.to_string(),
+            json_path: json_path.to_string(),
+            width: 10,
+            color_map: color_map.map(str::to_string),
+        }
+    }
+
+    fn compare_values(a: &str, b: &str) -> Ordering {
+        SortKey::parse(a, None).compare(&SortKey::parse(b, None))
+    }
+
+    fn names(items: &[Value]) -> Vec<&str> {
+        items.iter().map(|i| i["name"].as_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("10GB"), Some(10e9));
+        assert_eq!(parse_size("1.5 GiB"), Some(1.5 * 1024f64.powi(3)));
+        assert_eq!(parse_size("512M"), Some(512e6));
+        assert_eq!(parse_size("100 B"), Some(100.0));
+        assert_eq!(parse_size("10"), None);
+        assert_eq!(parse_size("n2-standard-4"), None);
+        assert_eq!(parse_size("2 apples"), None);
+    }
+
+    #[test]
+    fn test_compare_values() {
+        // Numbers by magnitude, not lexicographically
+        assert_eq!(compare_values("9", "10"), Ordering::Less);
+        // Sizes with different units
+        assert_eq!(compare_values("900MB", "1GB"), Ordering::Less);
+        // Timestamps with different offsets and precision
+        assert_eq!(
+            compare_values("2024-01-01T10:00:00.500-08:00", "2024-01-01T17:00:00Z"),
+            Ordering::Greater
+        );
+        // Text ignores case
+        assert_eq!(compare_values("alpha", "Beta"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_sort_items() {
+        let mut items = vec![
+            json!({"name": "b", "sizeGb": "100"}),
+            json!({"name": "none"}),
+            json!({"name": "a", "sizeGb": "20"}),
+            json!({"name": "c", "sizeGb": "3"}),
+        ];
+        let col = column("sizeGb", None);
+
+        sort_items(&mut items, &col, false);
+        assert_eq!(names(&items), vec!["c", "a", "b", "none"]);
+
+        // Missing values stay last when descending
+        sort_items(&mut items, &col, true);
+        assert_eq!(names(&items), vec!["b", "a", "c", "none"]);
+    }
+
+    #[test]
+    fn test_sort_items_by_status() {
+        let mut items = vec![
+            json!({"name": "stopped", "status": "STOPPED"}),
+            json!({"name": "odd", "status": "WEIRD"}),
+            json!({"name": "running", "status": "RUNNING"}),
+        ];
+        sort_items(&mut items, &column("status", Some("status")), false);
+        // Color map order rather than the alphabet, unknown values after
+        assert_eq!(names(&items), vec!["running", "stopped", "odd"]);
+    }
+}
@@ -64,9 +64,15 @@ pub fn render(f: &mut Frame, app: &App) {
     f.render_widget(input, chunks[0]);
 
     // Suggestions list with scroll
-    let suggestions_block = Block::default()
+    let mut suggestions_block = Block::default()
         .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
         .border_style(Style::default().fg(Color::Cyan));
+    if let Some(hint) = app.command_hint() {
+        suggestions_block = suggestions_block.title_bottom(Span::styled(
+            format!(" {} ", hint),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
 
     let inner_height = suggestions_block.inner(chunks[1]).height as usize;
 
@@ -0,0 +1,183 @@
+use super::truncate_string;
+use crate::app::App;
+use crate::describe::{DocLine, TokenKind};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let view = &app.describe_view;
+
+    let title = match app.current_resource() {
+        Some(resource) => format!(
+            " {} Details [{}] ",
+            resource.display_name,
+            view.format.label()
+        ),
+        None => format!(" Details [{}] ", view.format.label()),
+    };
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ));
+    if let Some((text, color)) = status_text(app) {
+        block = block.title_bottom(Line::from(Span::styled(text, Style::default().fg(color))));
+    }
+
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+
+    let Some(data) = &app.describe_data else {
+        f.render_widget(Paragraph::new("No item selected"), inner_area);
+        return;
+    };
+
+    let lines = view.lines(data);
+    let total_lines = lines.len();
+    let visible_lines = inner_area.height as usize;
+    view.viewport.set(visible_lines);
+
+    // Keep the cursor visible even if the terminal was resized since the last move
+    let scroll = view
+        .scroll
+        .min(view.cursor)
+        .max((view.cursor + 1).saturating_sub(visible_lines));
+
+    let query = if view.search_input {
+        String::new()
+    } else {
+        view.search.to_lowercase()
+    };
+
+    let rendered: Vec<Line> = lines
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible_lines)
+        .map(|(i, line)| render_line(line, &query, i == view.cursor))
+        .collect();
+
+    f.render_widget(Paragraph::new(rendered), inner_area);
+
+    // Render scrollbar if content exceeds visible area
+    if total_lines > visible_lines {
+        let max_scroll = total_lines.saturating_sub(visible_lines);
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓"));
+        let mut scrollbar_state =
+            ScrollbarState::new(max_scroll + visible_lines).position(scroll.min(max_scroll));
+        f.render_stateful_widget(scrollbar, inner_area, &mut scrollbar_state);
+    }
+}
+
+/// Loading, fetch error and search state shown at the bottom of the border
+fn status_text(app: &App) -> Option<(String, Color)> {
+    let view = &app.describe_view;
+    let mut parts = Vec::new();
+    let mut color = Color::DarkGray;
+
+    // The list item is shown until the full resource arrives
+    if app.describe_loading {
+        parts.push("loading full resource...".to_string());
+        color = Color::Yellow;
+    } else if let Some(e) = &app.describe_error {
+        parts.push(format!(
+            "showing list data, fetch failed: {}",
+            truncate_string(e, 60)
+        ));
+        color = Color::Red;
+    }
+
+    if view.search_input {
+        parts.push(format!("/{}_", view.search));
+        color = Color::Yellow;
+    } else if !view.search.is_empty() {
+        parts.push(match (view.current_match, view.matches.len()) {
+            (_, 0) => format!("no match for '{}'", view.search),
+            (Some(i), n) => format!("match {}/{} for '{}'", i + 1, n, view.search),
+            (None, n) => format!("{} matches for '{}'", n, view.search),
+        });
+    }
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some((format!(" {} ", parts.join(" | ")), color))
+    }
+}
+
+fn render_line(line: &DocLine, query: &str, selected: bool) -> Line<'static> {
+    let mut spans = vec![Span::raw(" ".repeat(line.indent))];
+    for (text, kind) in &line.tokens {
+        spans.extend(highlight_matches(text, token_style(*kind), query));
+    }
+
+    let line_out = Line::from(spans);
+    if selected {
+        line_out.style(Style::default().bg(Color::DarkGray))
+    } else {
+        line_out
+    }
+}
+
+/// Style for a syntax class
+fn token_style(kind: TokenKind) -> Style {
+    match kind {
+        TokenKind::Key => Style::default().fg(Color::Cyan), // Keys in cyan
+        TokenKind::String => Style::default().fg(Color::Green), // String values in green
+        TokenKind::Number => Style::default().fg(Color::LightBlue),
+        TokenKind::Bool => Style::default().fg(Color::Magenta),
+        TokenKind::Null | TokenKind::Comment => Style::default().fg(Color::DarkGray),
+        TokenKind::Punct => Style::default().fg(Color::White),
+    }
+}
+
+/// Split text into spans, highlighting case-insensitive occurrences of the query
+fn highlight_matches(text: &str, style: Style, query: &str) -> Vec<Span<'static>> {
+    let chars: Vec<char> = text.chars().collect();
+    let needle: Vec<char> = query.chars().collect();
+    if needle.is_empty() || chars.len() < needle.len() {
+        return vec![Span::styled(text.to_string(), style)];
+    }
+
+    let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let highlight = Style::default().fg(Color::Black).bg(Color::Yellow);
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let is_match = i + needle.len() <= chars.len()
+            && chars[i..i + needle.len()]
+                .iter()
+                .zip(&needle)
+                .all(|(c, n)| lower(*c) == *n);
+        if is_match {
+            if !plain.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut plain), style));
+            }
+            spans.push(Span::styled(
+                chars[i..i + needle.len()].iter().collect::<String>(),
+                highlight,
+            ));
+            i += needle.len();
+        } else {
+            plain.push(chars[i]);
+            i += 1;
+        }
+    }
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, style));
+    }
+    spans
+}
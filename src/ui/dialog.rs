@@ -1,4 +1,5 @@
 use crate::app::{App, Mode};
+use crate::resource::registry::ParamType;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -11,6 +12,7 @@ pub fn render(f: &mut Frame, app: &App) {
     match app.mode {
         Mode::Confirm => render_confirm_dialog(f, app),
         Mode::Warning => render_warning_dialog(f, app),
+        Mode::Input => render_input_dialog(f, app),
         _ => {}
     }
 }
@@ -20,7 +22,16 @@ fn render_confirm_dialog(f: &mut Frame, app: &App) {
         return;
     };
 
-    let area = centered_rect(60, 9, f.area());
+    // Bulk actions list their targets, one per line
+    let message_lines: Vec<Line> = pending
+        .message
+        .lines()
+        .map(|line| Line::from(Span::styled(line, Style::default().fg(Color::White))))
+        .collect();
+    let height = (8 + message_lines.len()).min(f.area().height.saturating_sub(2) as usize) as u16;
+    let (message_lines, hint) = scroll_message(app, message_lines, height.saturating_sub(6));
+
+    let area = centered_rect(60, height, f.area());
 
     f.render_widget(Clear, area);
 
@@ -51,7 +62,7 @@ fn render_confirm_dialog(f: &mut Frame, app: &App) {
     };
 
     // Build the dialog content
-    let text = vec![
+    let mut text = vec![
         Line::from(Span::styled(
             format!("<{}>", title),
             Style::default()
@@ -59,17 +70,14 @@ fn render_confirm_dialog(f: &mut Frame, app: &App) {
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
-        Line::from(Span::styled(
-            &pending.message,
-            Style::default().fg(Color::White),
-        )),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled(" Cancel ", cancel_style),
-            Span::raw("    "),
-            Span::styled(" OK ", ok_style),
-        ]),
     ];
+    text.extend(message_lines);
+    text.push(hint);
+    text.push(Line::from(vec![
+        Span::styled(" Cancel ", cancel_style),
+        Span::raw("    "),
+        Span::styled(" OK ", ok_style),
+    ]));
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -83,29 +91,32 @@ fn render_confirm_dialog(f: &mut Frame, app: &App) {
 }
 
 fn render_warning_dialog(f: &mut Frame, app: &App) {
-    // Use warning_message or error
+    // Use warning_message, error or info_message
     let (title, message, title_color, border_color) = if let Some(msg) = &app.warning_message {
         ("Warning", msg.clone(), Color::Yellow, Color::Yellow)
     } else if let Some(err) = &app.error {
         ("Error", err.clone(), Color::Red, Color::Red)
+    } else if let Some(info) = &app.info_message {
+        ("Info", info.clone(), Color::Cyan, Color::Cyan)
     } else {
         return;
     };
 
-    // Calculate height based on message length (wrap at ~50 chars)
-    let lines = (message.len() / 50) + 1;
-    let height = (6 + lines).min(15) as u16;
+    // Wrap long messages, keeping explicit line breaks
+    let wrapped_lines: Vec<Line> = message
+        .lines()
+        .flat_map(|line| wrap_text(line, 60))
+        .map(|line| Line::from(Span::styled(line, Style::default().fg(Color::White))))
+        .collect();
+
+    // Height based on wrapped line count, bounded by the terminal
+    let height = (6 + wrapped_lines.len()).min(f.area().height.saturating_sub(2) as usize) as u16;
+    let (wrapped_lines, hint) = scroll_message(app, wrapped_lines, height.saturating_sub(6));
 
     let area = centered_rect(70, height, f.area());
 
     f.render_widget(Clear, area);
 
-    // Wrap long messages
-    let wrapped_lines: Vec<Line> = wrap_text(&message, 60)
-        .into_iter()
-        .map(|line| Line::from(Span::styled(line, Style::default().fg(Color::White))))
-        .collect();
-
     let mut text = vec![
         Line::from(Span::styled(
             format!(" {} ", title),
@@ -118,7 +129,7 @@ fn render_warning_dialog(f: &mut Frame, app: &App) {
     ];
 
     text.extend(wrapped_lines);
-    text.push(Line::from(""));
+    text.push(hint);
     text.push(Line::from(vec![Span::styled(
         " OK (Enter/Esc) ",
         Style::default().fg(Color::Black).bg(Color::Magenta),
@@ -135,6 +146,117 @@ fn render_warning_dialog(f: &mut Frame, app: &App) {
     f.render_widget(paragraph, area);
 }
 
+fn render_input_dialog(f: &mut Frame, app: &App) {
+    let Some(form) = &app.action_form else {
+        return;
+    };
+
+    // Title, blank, fields (label + input each), blank, error/hint, borders
+    let height = (form.fields.len() * 2 + 7).min(25) as u16;
+    let area = centered_rect(60, height, f.area());
+
+    f.render_widget(Clear, area);
+
+    let mut text = vec![
+        Line::from(Span::styled(
+            format!("<{}>", form.title),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    for (i, field) in form.fields.iter().enumerate() {
+        let focused = i == form.focused;
+        let label_style = if focused {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let hint = match field.param.param_type {
+            ParamType::String => "",
+            ParamType::Integer => " (number)",
+            ParamType::Timestamp => " (RFC3339)",
+            ParamType::Enum => " (←/→)",
+        };
+        let optional = if field.param.required {
+            ""
+        } else {
+            " [optional]"
+        };
+        text.push(Line::from(Span::styled(
+            format!("{}{}{}", field.param.label, hint, optional),
+            label_style,
+        )));
+
+        let value = if field.param.param_type == ParamType::Enum {
+            format!("< {} >", field.value)
+        } else if focused {
+            format!("{}_", field.value)
+        } else {
+            field.value.clone()
+        };
+        let value_style = if focused {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        text.push(Line::from(Span::styled(
+            format!(" {} ", value),
+            value_style,
+        )));
+    }
+
+    text.push(Line::from(""));
+    match &form.error {
+        Some(error) => text.push(Line::from(Span::styled(
+            error.clone(),
+            Style::default().fg(Color::Red),
+        ))),
+        None => text.push(Line::from(Span::styled(
+            "Tab: next field | Enter: submit | Esc: cancel",
+            Style::default().fg(Color::DarkGray),
+        ))),
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center);
+
+    f.render_widget(paragraph, area);
+}
+
+/// Message lines of a dialog with room for `visible` of them, from the scroll
+/// position, and the line below them: blank, or where the view is when it scrolls
+fn scroll_message<'a>(app: &App, lines: Vec<Line<'a>>, visible: u16) -> (Vec<Line<'a>>, Line<'a>) {
+    let visible = visible as usize;
+    let max_scroll = lines.len().saturating_sub(visible);
+    app.dialog_max_scroll.set(max_scroll);
+    if max_scroll == 0 {
+        return (lines, Line::from(""));
+    }
+
+    let scroll = app.dialog_scroll.min(max_scroll);
+    let hint = Line::from(Span::styled(
+        format!(
+            "lines {}-{} of {}, ↑/↓ to scroll",
+            scroll + 1,
+            scroll + visible,
+            lines.len()
+        ),
+        Style::default().fg(Color::DarkGray),
+    ));
+    let lines = lines.into_iter().skip(scroll).take(visible).collect();
+    (lines, hint)
+}
+
 /// Wrap text to fit within a given width
 fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
     let mut lines = Vec::new();
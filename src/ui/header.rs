@@ -1,4 +1,6 @@
 use crate::app::App;
+use crate::gcp::auth::TokenProvider;
+use crate::gcp::client::region_label;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -7,6 +9,11 @@ use ratatui::{
     Frame,
 };
 
+/// Rows the header needs: at least 7, more when the context column is longer
+pub fn height(app: &App) -> u16 {
+    (context_lines(app).len() as u16).max(7)
+}
+
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
     // Split header into 5 columns like k9s
     let columns = Layout::default()
@@ -28,12 +35,40 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_context_column(f: &mut Frame, app: &App, area: Rect) {
+    let paragraph = Paragraph::new(context_lines(app));
+    f.render_widget(paragraph, area);
+}
+
+/// Context, project, auth, zone, region and resource, then optional state lines
+fn context_lines(app: &App) -> Vec<Line<'_>> {
     let resource_name = app
         .current_resource()
         .map(|r| r.display_name.as_str())
         .unwrap_or(&app.resource_key);
 
-    let mut lines = vec![
+    let mut lines = Vec::new();
+
+    // Active named context, red when it is read-only
+    if let Some(context) = &app.config.active_context {
+        let (label, bg) = if app.readonly {
+            (format!(" {} (readonly) ", context), Color::Red)
+        } else {
+            (format!(" {} ", context), Color::Cyan)
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Context:", Style::default().fg(Color::DarkGray)),
+            Span::raw(" "),
+            Span::styled(
+                label,
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(bg)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+
+    lines.extend([
         Line::from(vec![
             Span::styled("Project:", Style::default().fg(Color::DarkGray)),
             Span::raw(" "),
@@ -44,11 +79,22 @@ fn render_context_column(f: &mut Frame, app: &App, area: Rect) {
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
+        auth_line(),
         Line::from(vec![
             Span::styled("Zone:   ", Style::default().fg(Color::DarkGray)),
             Span::raw(" "),
             Span::styled(
-                &app.zone,
+                app.zone_label(),
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Region: ", Style::default().fg(Color::DarkGray)),
+            Span::raw(" "),
+            Span::styled(
+                region_label(&app.region),
                 Style::default()
                     .fg(Color::Magenta)
                     .add_modifier(Modifier::BOLD),
@@ -64,19 +110,31 @@ fn render_context_column(f: &mut Frame, app: &App, area: Rect) {
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
-    ];
+    ]);
 
     // Show parent context if navigating
     if let Some(parent) = &app.parent_context {
         lines.push(Line::from(vec![
-            Span::styled("Context:", Style::default().fg(Color::DarkGray)),
+            Span::styled("Parent: ", Style::default().fg(Color::DarkGray)),
             Span::raw(" "),
             Span::styled(&parent.display_name, Style::default().fg(Color::Yellow)),
         ]));
     }
 
-    // Show read-only mode indicator
-    if app.readonly {
+    // Show running operations
+    let running = app.running_operations();
+    if running > 0 {
+        lines.push(Line::from(vec![
+            Span::styled("Ops:     ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{} running (:ops)", running),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]));
+    }
+
+    // Show read-only mode indicator (the context badge shows it otherwise)
+    if app.readonly && app.config.active_context.is_none() {
         lines.push(Line::from(vec![
             Span::styled("Mode:    ", Style::default().fg(Color::DarkGray)),
             Span::styled(
@@ -88,8 +146,31 @@ fn render_context_column(f: &mut Frame, app: &App, area: Rect) {
         ]));
     }
 
-    let paragraph = Paragraph::new(lines);
-    f.render_widget(paragraph, area);
+    lines
+}
+
+/// Principal and credential source of the current token, "-" before the first call
+fn auth_line() -> Line<'static> {
+    let mut spans = vec![
+        Span::styled("Auth:   ", Style::default().fg(Color::DarkGray)),
+        Span::raw(" "),
+    ];
+    match TokenProvider::identity() {
+        Some(identity) => {
+            spans.push(Span::styled(
+                identity
+                    .principal
+                    .unwrap_or_else(|| "unknown principal".to_string()),
+                Style::default().fg(Color::Magenta),
+            ));
+            spans.push(Span::styled(
+                format!(" ({})", identity.source),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        None => spans.push(Span::styled("-", Style::default().fg(Color::DarkGray))),
+    }
+    Line::from(spans)
 }
 
 fn render_shortcuts_column(f: &mut Frame, app: &App, area: Rect) {
@@ -106,19 +187,26 @@ fn render_shortcuts_column(f: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_zone_shortcuts(f: &mut Frame, app: &App, area: Rect) {
-    let zones = [
-        ("0", "us-central1-a"),
-        ("1", "us-east1-b"),
-        ("2", "us-west1-a"),
-        ("3", "europe-west1-b"),
-        ("4", "asia-east1-a"),
-        ("5", "asia-northeast1-a"),
-    ];
+    // Favourite zones that fit above the all-zones line
+    let rows = (area.height as usize).saturating_sub(1);
+    let mut zones: Vec<(String, String)> = app
+        .config
+        .effective_favorite_zones()
+        .into_iter()
+        .take(rows)
+        .enumerate()
+        .map(|(i, zone)| (i.to_string(), zone))
+        .collect();
+    zones.push(("Z".to_string(), "all zones".to_string()));
 
     let lines: Vec<Line> = zones
         .iter()
         .map(|(key, zone)| {
-            let is_current = *zone == app.zone;
+            let is_current = if app.client.all_zones {
+                key == "Z"
+            } else {
+                *zone == app.zone
+            };
             let style = if is_current {
                 Style::default()
                     .fg(Color::Green)
@@ -130,7 +218,7 @@ fn render_zone_shortcuts(f: &mut Frame, app: &App, area: Rect) {
             Line::from(vec![
                 Span::styled(format!("<{}>", key), Style::default().fg(Color::Yellow)),
                 Span::raw(" "),
-                Span::styled(*zone, style),
+                Span::styled(zone.as_str(), style),
             ])
         })
         .collect();
@@ -22,9 +22,14 @@ pub fn render(f: &mut Frame, _app: &App) {
         Line::from(""),
         create_section("Views"),
         create_key_line("d / Enter", "Describe item"),
+        create_key_line("yy / yi / yl", "Copy name / id / link"),
+        create_key_line("y1-9 / yj", "Copy column / item JSON"),
+        create_key_line("o", "Open in Cloud Console"),
         create_key_line("?", "Toggle help"),
         Line::from(""),
         create_section("Actions"),
+        create_key_line("Space", "Mark item (actions run on all marked)"),
+        create_key_line("Ctrl+a", "Mark all filtered items"),
         create_key_line("s", "Start instance"),
         create_key_line("x", "Stop instance"),
         create_key_line("Ctrl+d", "Delete (destructive)"),
@@ -34,7 +39,11 @@ pub fn render(f: &mut Frame, _app: &App) {
         Line::from(""),
         create_section("Modes"),
         create_key_line("/", "Filter mode"),
+        create_key_line("< / >", "Sort by previous/next column"),
+        create_key_line("~", "Reverse sort order"),
         create_key_line(":", "Resources mode"),
+        create_key_line(":filter <expr>", "Server-side filter"),
+        create_key_line(":export <path>", "Export table to a file"),
         Line::from(""),
         create_section("Resources"),
         create_key_line(":vm-instances", "Compute Engine VMs"),
@@ -49,8 +58,16 @@ pub fn render(f: &mut Frame, _app: &App) {
         create_key_line(":service-accounts", "IAM accounts"),
         Line::from(""),
         create_section("Navigation"),
-        create_key_line(":projects", "Select project"),
-        create_key_line(":zones", "Select zone"),
+        create_key_line(":ctx <name>", "Switch context"),
+        create_key_line(":whoami", "Principal, scopes, expiry"),
+        create_key_line(":projects", "Select project (type to search)"),
+        create_key_line("Tab / Ctrl+p", "Folder tree / pin project"),
+        create_key_line(":zones", "Select zone (/ to search)"),
+        create_key_line("0-9", "Favourite zones"),
+        create_key_line(":regions", "Select region"),
+        create_key_line("Z / :zone all", "Toggle all zones"),
+        create_key_line(":region <r|all>", "Switch region"),
+        create_key_line(":operations", "Running operations"),
         Line::from(""),
         create_key_line("Esc", "Close / Cancel"),
         create_key_line("Ctrl+c", "Quit application"),
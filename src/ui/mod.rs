@@ -1,7 +1,9 @@
 mod command_box;
+mod describe;
 mod dialog;
 mod header;
 mod help;
+mod operations;
 mod projects;
 pub mod splash;
 mod zones;
@@ -12,20 +14,18 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{
-        Block, Borders, Cell, Paragraph, Row, Scrollbar, ScrollbarOrientation, ScrollbarState,
-        Table, TableState,
-    },
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
     Frame,
 };
+use std::ops::Range;
 
 pub fn render(f: &mut Frame, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(6), // Header (multi-line)
-            Constraint::Min(1),    // Main content (table or describe)
-            Constraint::Length(1), // Footer/crumb
+            Constraint::Length(header::height(app)), // Header (multi-line)
+            Constraint::Min(1),                      // Main content (table or describe)
+            Constraint::Length(1),                   // Footer/crumb
         ])
         .split(f.area());
 
@@ -41,7 +41,10 @@ pub fn render(f: &mut Frame, app: &App) {
             zones::render(f, app, chunks[1]);
         }
         Mode::Describe => {
-            render_describe_view(f, app, chunks[1]);
+            describe::render(f, app, chunks[1]);
+        }
+        Mode::Operations => {
+            operations::render(f, app, chunks[1]);
         }
         _ => {
             render_main_content(f, app, chunks[1]);
@@ -56,7 +59,7 @@ pub fn render(f: &mut Frame, app: &App) {
         Mode::Help => {
             help::render(f, app);
         }
-        Mode::Confirm | Mode::Warning => {
+        Mode::Confirm | Mode::Warning | Mode::Input => {
             dialog::render(f, app);
         }
         Mode::Command => {
@@ -98,7 +101,16 @@ fn render_filter_bar(f: &mut Frame, app: &App, area: Rect) {
         format!("/{}", app.filter_text)
     };
 
-    let paragraph = Paragraph::new(Line::from(vec![Span::styled(filter_display, cursor_style)]));
+    let mut spans = vec![Span::styled(filter_display, cursor_style)];
+    // Unparsable expressions are matched as plain text, say why
+    if let Some(e) = &app.filter.error {
+        spans.push(Span::styled(
+            format!("  ({}, matching as text)", e),
+            Style::default().fg(Color::Red),
+        ));
+    }
+
+    let paragraph = Paragraph::new(Line::from(spans));
     f.render_widget(paragraph, area);
 }
 
@@ -115,13 +127,20 @@ fn render_dynamic_table(f: &mut Frame, app: &App, area: Rect) {
         let count = app.filtered_items.len();
         let total = app.items.len();
 
-        if app.filter_text.is_empty() {
-            format!(" {}({})[{}] ", resource.display_name, app.zone, count)
+        let mut counts = if app.filter_text.is_empty() {
+            format!("[{}]", count)
         } else {
-            format!(
-                " {}({})[{}/{}] ",
-                resource.display_name, app.zone, count, total
-            )
+            format!("[{}/{}]", count, total)
+        };
+        if !app.marked.is_empty() {
+            counts.push_str(&format!("[{} marked]", app.marked_shown()));
+        }
+        match app.active_server_filter() {
+            Some(expr) => format!(
+                " {}({}){} <{}> ",
+                resource.display_name, app.zone, counts, expr
+            ),
+            None => format!(" {}({}){} ", resource.display_name, app.zone, counts),
         }
     };
 
@@ -141,8 +160,13 @@ fn render_dynamic_table(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(block, area);
 
     // Build header from column definitions with left padding (uppercase)
-    let header_cells = resource.columns.iter().map(|col| {
-        Cell::from(format!(" {}", col.header.to_uppercase())).style(
+    let header_cells = resource.columns.iter().enumerate().map(|(i, col)| {
+        let arrow = match app.sort {
+            Some(sort) if sort.column == i && sort.descending => " ▼",
+            Some(sort) if sort.column == i => " ▲",
+            _ => "",
+        };
+        Cell::from(format!(" {}{}", col.header.to_uppercase(), arrow)).style(
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
@@ -152,13 +176,26 @@ fn render_dynamic_table(f: &mut Frame, app: &App, area: Rect) {
 
     // Build rows from filtered items with left padding
     let rows = app.filtered_items.iter().map(|item| {
-        let cells = resource.columns.iter().map(|col| {
+        let marked = app.is_marked(item);
+        let cells = resource.columns.iter().enumerate().map(|(i, col)| {
             let value = extract_json_value(item, &col.json_path);
             let style = get_cell_style(&value, col);
-            let display_value = format_cell_value(&value, col);
-            Cell::from(format!(" {}", truncate_string(&display_value, 38))).style(style)
+            let display_value = truncate_string(&format_cell_value(&value, col), 38);
+            let highlights = app.filter.highlights(resource, &col.json_path, &value);
+            // Marked rows carry a marker in the padding of the first column
+            let pad = if marked && i == 0 { "*" } else { " " };
+            Cell::from(highlight_cell(pad, &display_value, &highlights)).style(style)
         });
-        Row::new(cells)
+        let row = Row::new(cells);
+        if marked {
+            row.style(
+                Style::default()
+                    .fg(Color::LightMagenta)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            row
+        }
     });
 
     // Build column widths
@@ -181,6 +218,32 @@ fn render_dynamic_table(f: &mut Frame, app: &App, area: Rect) {
     f.render_stateful_widget(table, inner_area, &mut state);
 }
 
+/// Build a cell line with left padding, marking the byte ranges matched by the filter
+fn highlight_cell(pad: &str, text: &str, ranges: &[Range<usize>]) -> Line<'static> {
+    let highlight = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let mut spans = vec![Span::raw(pad.to_string())];
+    let mut pos = 0;
+    for range in ranges {
+        // Ranges refer to the full value, the text may be truncated
+        let end = range.end.min(text.len());
+        if range.start < pos
+            || range.start >= end
+            || !text.is_char_boundary(range.start)
+            || !text.is_char_boundary(end)
+        {
+            continue;
+        }
+        spans.push(Span::raw(text[pos..range.start].to_string()));
+        spans.push(Span::styled(text[range.start..end].to_string(), highlight));
+        pos = end;
+    }
+    spans.push(Span::raw(text[pos..].to_string()));
+    Line::from(spans)
+}
+
 /// Get cell style based on value and column definition
 fn get_cell_style(value: &str, col: &ColumnDef) -> Style {
     if let Some(ref color_map_name) = col.color_map {
@@ -223,183 +286,70 @@ fn truncate_string(s: &str, max_len: usize) -> String {
     }
 }
 
-fn render_describe_view(f: &mut Frame, app: &App, area: Rect) {
-    let json = app
-        .selected_item_json()
-        .unwrap_or_else(|| "No item selected".to_string());
-
-    // Apply JSON syntax highlighting
-    let lines: Vec<Line> = json.lines().map(highlight_json_line).collect();
-    let total_lines = lines.len();
-
-    let title = if let Some(resource) = app.current_resource() {
-        format!(" {} Details ", resource.display_name)
-    } else {
-        " Details ".to_string()
-    };
-
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
-        .title(Span::styled(
-            title,
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        ));
-
-    let inner_area = block.inner(area);
-    f.render_widget(block, area);
-
-    // Calculate max scroll based on inner area (content area without borders)
-    let visible_lines = inner_area.height as usize;
-    let max_scroll = total_lines.saturating_sub(visible_lines);
-    let scroll = app.describe_scroll.min(max_scroll);
-
-    let paragraph = Paragraph::new(lines.clone()).scroll((scroll as u16, 0));
-
-    f.render_widget(paragraph, inner_area);
-
-    // Render scrollbar if content exceeds visible area
-    if total_lines > visible_lines {
-        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-            .begin_symbol(Some("↑"))
-            .end_symbol(Some("↓"));
-        let mut scrollbar_state = ScrollbarState::new(max_scroll + visible_lines).position(scroll);
-        f.render_stateful_widget(scrollbar, inner_area, &mut scrollbar_state);
-    }
-}
-
-/// Apply JSON syntax highlighting to a single line
-fn highlight_json_line(line: &str) -> Line<'static> {
-    let mut spans: Vec<Span<'static>> = Vec::new();
-    let mut chars = line.chars().peekable();
-    let mut current = String::new();
-    let mut is_key = true; // Track if we're parsing a key or value
-
-    while let Some(c) = chars.next() {
-        match c {
-            '"' => {
-                if !current.is_empty() {
-                    spans.push(Span::raw(current.clone()));
-                    current.clear();
-                }
-
-                // Collect the entire string
-                let mut string_content = String::from("\"");
-                while let Some(&next_c) = chars.peek() {
-                    chars.next();
-                    string_content.push(next_c);
-                    if next_c == '"' {
-                        break;
-                    }
-                    if next_c == '\\' {
-                        if let Some(&escaped) = chars.peek() {
-                            chars.next();
-                            string_content.push(escaped);
-                        }
-                    }
-                }
-
-                // Color based on whether it's a key or value
-                let style = if is_key {
-                    Style::default().fg(Color::Cyan) // Keys in cyan
-                } else {
-                    Style::default().fg(Color::Green) // String values in green
-                };
-                spans.push(Span::styled(string_content, style));
-            }
-            ':' => {
-                current.push(c);
-                spans.push(Span::styled(
-                    current.clone(),
-                    Style::default().fg(Color::White),
-                ));
-                current.clear();
-                is_key = false; // After colon, we're parsing a value
-            }
-            ',' => {
-                if !current.is_empty() {
-                    // Check if it's a number or keyword
-                    let style = get_json_value_style(&current);
-                    spans.push(Span::styled(current.clone(), style));
-                    current.clear();
-                }
-                spans.push(Span::styled(
-                    ",".to_string(),
-                    Style::default().fg(Color::White),
-                ));
-                is_key = true; // After comma, next string is a key
-            }
-            '{' | '}' | '[' | ']' => {
-                if !current.is_empty() {
-                    let style = get_json_value_style(&current);
-                    spans.push(Span::styled(current.clone(), style));
-                    current.clear();
-                }
-                spans.push(Span::styled(
-                    c.to_string(),
-                    Style::default().fg(Color::Yellow),
-                ));
-                if c == '{' || c == '[' {
-                    is_key = c == '{'; // After {, next is key; after [, next is value
-                }
-            }
-            ' ' | '\t' => {
-                if !current.is_empty() {
-                    let style = get_json_value_style(&current);
-                    spans.push(Span::styled(current.clone(), style));
-                    current.clear();
-                }
-                spans.push(Span::raw(c.to_string()));
-            }
-            _ => {
-                current.push(c);
-            }
-        }
-    }
-
-    if !current.is_empty() {
-        let style = get_json_value_style(&current);
-        spans.push(Span::styled(current, style));
-    }
-
-    Line::from(spans)
-}
-
-/// Get style for JSON values (numbers, booleans, null)
-fn get_json_value_style(value: &str) -> Style {
-    let trimmed = value.trim();
-    if trimmed == "null" {
-        Style::default().fg(Color::DarkGray)
-    } else if trimmed == "true" || trimmed == "false" {
-        Style::default().fg(Color::Magenta)
-    } else if trimmed.parse::<f64>().is_ok() {
-        Style::default().fg(Color::LightBlue)
-    } else {
-        Style::default().fg(Color::White)
-    }
-}
-
 fn render_crumb(f: &mut Frame, app: &App, area: Rect) {
     // Build breadcrumb from navigation
     let breadcrumb = app.get_breadcrumb();
     let crumb_display = breadcrumb.join(" > ");
 
-    let status_text = if app.loading {
-        "Loading...".to_string()
+    let hint = Style::default().fg(Color::DarkGray);
+    let highlight = Style::default().fg(Color::Yellow);
+
+    let (status_text, style) = if app.yank_pending {
+        (
+            "copy: y/n: name | i: id | l: link | 1-9: column | j: JSON | Esc: cancel".to_string(),
+            highlight,
+        )
+    } else if app.loading {
+        let text = match app.list_progress {
+            Some(p) if p.pages > 0 => {
+                format!("Loading... page {} ({} items)", p.pages + 1, p.items)
+            }
+            _ => "Loading...".to_string(),
+        };
+        (text, highlight)
+    } else if let Some(n) = app.active_notification() {
+        let color = if n.is_error { Color::Red } else { Color::Green };
+        (n.message.clone(), Style::default().fg(color))
     } else if app.mode == Mode::Describe {
-        "j/k: scroll | d/Esc: back".to_string()
+        (
+            "Enter: fold | h/l: close/open | c/e: all | t: JSON/YAML | /: search | n/N: next/prev | y/Y: copy path/all | o: console | Esc: back"
+                .to_string(),
+            hint,
+        )
+    } else if app.mode == Mode::Input {
+        (
+            "Tab: next field | ←/→: change option | Enter: submit | Esc: cancel".to_string(),
+            hint,
+        )
+    } else if app.mode == Mode::Operations {
+        (
+            "Enter: show error | c: clear finished | Esc: back".to_string(),
+            hint,
+        )
     } else if app.filter_active {
-        "Type to filter | Esc: clear".to_string()
-    } else {
-        String::new()
-    };
-
-    let style = if app.loading {
-        Style::default().fg(Color::Yellow)
+        (
+            "status=RUNNING, a|b, !x, name~/re/ | Enter: apply | Esc: clear".to_string(),
+            hint,
+        )
+    } else if !app.marked.is_empty() {
+        let shown = app.marked_shown();
+        let hidden = match app.marked.len().saturating_sub(shown) {
+            0 => String::new(),
+            n => format!(" ({} hidden by the filter)", n),
+        };
+        let text = format!(
+            "{} marked{} | actions run on the marked rows shown | Space: mark | Ctrl+a: all | Esc: unmark",
+            shown, hidden
+        );
+        (text, highlight)
+    } else if let Some(p) = app.list_progress.filter(|p| p.truncated) {
+        let text = format!(
+            "Showing first {} pages ({} items) | raise max_pages in config for more",
+            p.pages, p.items
+        );
+        (text, highlight)
     } else {
-        Style::default().fg(Color::DarkGray)
+        (String::new(), hint)
     };
 
     let crumb = Line::from(vec![
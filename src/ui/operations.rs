@@ -0,0 +1,93 @@
+use crate::app::App;
+use crate::gcp::operations::OperationState;
+use ratatui::{
+    layout::{Alignment, Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    // Create bordered box with centered title
+    let title = format!(
+        " Operations[{}/{}] ",
+        app.running_operations(),
+        app.operations.len()
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_alignment(Alignment::Center);
+
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+
+    if app.operations.is_empty() {
+        let msg = Paragraph::new(" No operations started in this session")
+            .style(Style::default().fg(Color::DarkGray));
+        f.render_widget(msg, inner_area);
+        return;
+    }
+
+    let header_cells = [" ACTION", " STATUS", " PROGRESS", " ELAPSED", " OPERATION"]
+        .iter()
+        .map(|h| {
+            Cell::from(*h).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        });
+    let header = Row::new(header_cells).height(1);
+
+    let rows = app.operations.iter().map(|op| {
+        let (status, color) = match op.status.state {
+            OperationState::Running => ("RUNNING ↻", Color::Yellow),
+            OperationState::Done => ("DONE", Color::Green),
+            OperationState::Failed => ("FAILED", Color::Red),
+        };
+        let progress = op
+            .status
+            .progress
+            .map(|p| format!("{}%", p))
+            .unwrap_or_else(|| "-".to_string());
+        let elapsed = op.elapsed().as_secs();
+        let elapsed = format!("{}m{:02}s", elapsed / 60, elapsed % 60);
+        let name = op.op.name.split('/').next_back().unwrap_or(&op.op.name);
+
+        Row::new(vec![
+            Cell::from(format!(" {}", op.description)),
+            Cell::from(format!(" {}", status)).style(Style::default().fg(color)),
+            Cell::from(format!(" {}", progress)),
+            Cell::from(format!(" {}", elapsed)),
+            Cell::from(format!(" {}", name)).style(Style::default().fg(Color::DarkGray)),
+        ])
+    });
+
+    let widths = [
+        Constraint::Percentage(35),
+        Constraint::Percentage(12),
+        Constraint::Percentage(10),
+        Constraint::Percentage(10),
+        Constraint::Percentage(33),
+    ];
+
+    let table = Table::new(rows, widths).header(header).row_highlight_style(
+        Style::default()
+            .bg(Color::DarkGray)
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let mut state = TableState::default();
+    state.select(Some(app.operations_selected));
+
+    f.render_stateful_widget(table, inner_area, &mut state);
+}
@@ -1,4 +1,5 @@
 use crate::app::App;
+use crate::projects::{PickerRow, ProjectView};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -8,8 +9,19 @@ use ratatui::{
 };
 
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let picker = &app.project_picker;
+    let rows = app.project_rows();
+    let project_count = rows
+        .iter()
+        .filter(|row| matches!(row, PickerRow::Project { .. }))
+        .count();
+
     // Create bordered box with centered title
-    let title = format!(" Select Project[{}] ", app.available_projects.len());
+    let view = match picker.view {
+        ProjectView::List => "",
+        ProjectView::Tree => " Tree",
+    };
+    let title = format!(" Select Project[{}]{} ", project_count, view);
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan))
@@ -24,40 +36,61 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
     let inner_area = block.inner(area);
     f.render_widget(block, area);
 
-    // Split into help text and table
+    // Split into help text, search and table
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(2), Constraint::Min(1)])
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Min(1),
+        ])
         .split(inner_area);
 
     // Help text
+    let keys = "Enter: select, Tab: list/tree, Ctrl+p: pin";
     let help_text = if app.project.is_empty() {
         Line::from(vec![
             Span::styled(
                 " Select a project to get started. ",
                 Style::default().fg(Color::Yellow),
             ),
-            Span::styled(
-                "Press Enter to select, Esc to cancel.",
-                Style::default().fg(Color::DarkGray),
-            ),
+            Span::styled(keys, Style::default().fg(Color::DarkGray)),
         ])
     } else {
         Line::from(vec![
             Span::styled(" Current: ", Style::default().fg(Color::DarkGray)),
             Span::styled(&app.project, Style::default().fg(Color::Green)),
             Span::styled(
-                " | Enter: select, Esc: cancel",
+                format!(" | {}, Esc: cancel", keys),
                 Style::default().fg(Color::DarkGray),
             ),
         ])
     };
-    let help = Paragraph::new(help_text);
-    f.render_widget(help, chunks[0]);
+    f.render_widget(Paragraph::new(help_text), chunks[0]);
+
+    let search = Line::from(vec![
+        Span::styled(" Search: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(&picker.query, Style::default().fg(Color::Yellow)),
+        Span::styled("▏", Style::default().fg(Color::Yellow)),
+    ]);
+    f.render_widget(Paragraph::new(search), chunks[1]);
+
+    if rows.is_empty() {
+        let message = if picker.loading {
+            " Loading organizations and folders..."
+        } else if app.available_projects.is_empty() {
+            " No projects found"
+        } else {
+            " No match"
+        };
+        let paragraph = Paragraph::new(message).style(Style::default().fg(Color::DarkGray));
+        f.render_widget(paragraph, chunks[2]);
+        return;
+    }
 
     // Table header
-    let header_cells = [" PROJECT"].iter().map(|h| {
-        Cell::from(*h).style(
+    let header_cells = [" PROJECT", "NAME", "NUMBER", ""].into_iter().map(|h| {
+        Cell::from(h).style(
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
@@ -67,35 +100,82 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
     let header = Row::new(header_cells).height(1);
 
     // Table rows
-    let rows = app.available_projects.iter().map(|project| {
-        let style = if project == &app.project {
-            Style::default().fg(Color::Green)
-        } else {
-            Style::default()
-        };
+    let table_rows = rows.iter().map(|row| match row {
+        PickerRow::Container {
+            index,
+            depth,
+            collapsed,
+        } => {
+            let container = &picker.containers[*index];
+            let arrow = if *collapsed { "▸" } else { "▾" };
+            let kind = if container.is_organization() {
+                "organization"
+            } else {
+                "folder"
+            };
+            Row::new(vec![
+                Cell::from(format!(
+                    "   {}{} {}",
+                    "  ".repeat(*depth),
+                    arrow,
+                    container.display_name
+                ))
+                .style(Style::default().fg(Color::Cyan)),
+                Cell::from(""),
+                Cell::from(""),
+                Cell::from(kind).style(Style::default().fg(Color::DarkGray)),
+            ])
+        }
+        PickerRow::Project { index, depth } => {
+            let project = &app.available_projects[*index];
+            let is_current = project.project_id == app.project;
+            let style = if is_current {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+            };
+            let marker = if is_current { " ● " } else { "   " };
+            let tag = if app.config.pinned_projects.contains(&project.project_id) {
+                "pinned"
+            } else if app.config.recent_projects.contains(&project.project_id) {
+                "recent"
+            } else {
+                ""
+            };
 
-        let marker = if project == &app.project {
-            " ● "
-        } else {
-            "   "
-        };
-
-        Row::new(vec![
-            Cell::from(format!("{}{}", marker, project)).style(style)
-        ])
+            Row::new(vec![
+                Cell::from(format!(
+                    "{}{}{}",
+                    marker,
+                    "  ".repeat(*depth),
+                    project.project_id
+                ))
+                .style(style),
+                Cell::from(project.display_name.as_str()),
+                Cell::from(project.number.as_str()).style(Style::default().fg(Color::DarkGray)),
+                Cell::from(tag).style(Style::default().fg(Color::Yellow)),
+            ])
+        }
     });
 
-    let widths = [Constraint::Percentage(100)];
+    let widths = [
+        Constraint::Percentage(40),
+        Constraint::Percentage(30),
+        Constraint::Percentage(16),
+        Constraint::Percentage(14),
+    ];
 
-    let table = Table::new(rows, widths).header(header).row_highlight_style(
-        Style::default()
-            .bg(Color::DarkGray)
-            .fg(Color::White)
-            .add_modifier(Modifier::BOLD),
-    );
+    let table = Table::new(table_rows, widths)
+        .header(header)
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        );
 
     let mut state = TableState::default();
-    state.select(Some(app.projects_selected));
+    state.select(Some(picker.selected));
 
-    f.render_stateful_widget(table, chunks[1], &mut state);
+    f.render_stateful_widget(table, chunks[2], &mut state);
 }
@@ -1,15 +1,21 @@
-use crate::app::App;
+use crate::app::{App, LocationKind};
 use ratatui::{
-    layout::{Alignment, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::Span,
-    widgets::{Block, Borders, Cell, Row, Table, TableState},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
     Frame,
 };
 
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let locations = app.filtered_locations();
+    let (title, name_header) = match app.location_kind {
+        LocationKind::Zone => ("Zones", " ZONE"),
+        LocationKind::Region => ("Regions", " REGION"),
+    };
+
     // Create bordered box with centered title
-    let title = format!(" Zones[{}] ", app.available_zones.len());
+    let title = format!(" {}[{}] ", title, locations.len());
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::DarkGray))
@@ -24,8 +30,39 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
     let inner_area = block.inner(area);
     f.render_widget(block, area);
 
-    let header_cells = [" ZONE"].iter().map(|h| {
-        Cell::from(*h).style(
+    // Split into search line and table
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(1)])
+        .split(inner_area);
+
+    let cursor = if app.location_searching { "▏" } else { "" };
+    let search = Line::from(vec![
+        Span::styled(" Search: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(&app.location_query, Style::default().fg(Color::Yellow)),
+        Span::styled(cursor, Style::default().fg(Color::Yellow)),
+        Span::styled(
+            "  /: search, Enter: select, j/k: move, Esc: cancel",
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(search), chunks[0]);
+
+    if locations.is_empty() {
+        let message = if app.locations_loading {
+            format!(" Loading {}...", app.location_kind.plural())
+        } else if app.picker_locations().is_empty() {
+            format!(" No {} found", app.location_kind.plural())
+        } else {
+            " No match".to_string()
+        };
+        let paragraph = Paragraph::new(message).style(Style::default().fg(Color::DarkGray));
+        f.render_widget(paragraph, chunks[1]);
+        return;
+    }
+
+    let header_cells = [name_header, "STATUS", "KEY"].into_iter().map(|h| {
+        Cell::from(h).style(
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
@@ -34,19 +71,45 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
 
     let header = Row::new(header_cells).height(1);
 
-    let rows = app.available_zones.iter().map(|zone| {
-        let style = if zone == &app.zone {
+    // Number keys of favourite zones
+    let favorites = match app.location_kind {
+        LocationKind::Zone => app.config.effective_favorite_zones(),
+        LocationKind::Region => Vec::new(),
+    };
+    let current = app.current_location();
+
+    let rows = locations.iter().map(|location| {
+        let is_current = location.name == current;
+        let style = if is_current {
             Style::default().fg(Color::Green)
         } else {
             Style::default()
         };
+        let marker = if is_current { " * " } else { "   " };
 
-        let marker = if zone == &app.zone { " * " } else { "   " };
+        let status_color = if location.is_up() {
+            Color::Green
+        } else {
+            Color::Red
+        };
+        let key = favorites
+            .iter()
+            .position(|z| z == &location.name)
+            .map(|i| format!("<{}>", i))
+            .unwrap_or_default();
 
-        Row::new(vec![Cell::from(format!("{}{}", marker, zone)).style(style)])
+        Row::new(vec![
+            Cell::from(format!("{}{}", marker, location.name)).style(style),
+            Cell::from(location.status.as_str()).style(Style::default().fg(status_color)),
+            Cell::from(key).style(Style::default().fg(Color::Yellow)),
+        ])
     });
 
-    let widths = [ratatui::layout::Constraint::Percentage(100)];
+    let widths = [
+        Constraint::Percentage(60),
+        Constraint::Percentage(25),
+        Constraint::Percentage(15),
+    ];
 
     let table = Table::new(rows, widths).header(header).row_highlight_style(
         Style::default()
@@ -58,5 +121,5 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
     let mut state = TableState::default();
     state.select(Some(app.zones_selected));
 
-    f.render_stateful_widget(table, inner_area, &mut state);
+    f.render_stateful_widget(table, chunks[1], &mut state);
 }